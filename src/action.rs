@@ -0,0 +1,125 @@
+use rhai::{Dynamic, Map};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::GameError;
+
+/// 정부, 시장, 다른 플레이어에게 나누어 지불하는 금액
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Payment {
+    #[serde(default)]
+    pub gov_amount: Option<i64>,
+    #[serde(default)]
+    pub market_amount: Option<i64>,
+    #[serde(default)]
+    pub player_amount: Option<i64>,
+    #[serde(default)]
+    pub to_player_id: Option<u32>,
+    pub message: String,
+}
+
+/// 턴 스크립트(`script_action`)가 반환하는 행동
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum TurnAction {
+    PromptBuy {
+        tile_name: String,
+        price: i64,
+        #[serde(default)]
+        free_flag: bool,
+        #[serde(default)]
+        ticket_flag: bool,
+    },
+    /// 도착한 타일의 소유자에게 통행료를 지불
+    PayRent,
+    PayTax { amount: i64 },
+    Imprison,
+    WarpToPosition { position: u32 },
+    PayTo(Payment),
+    PayToAll { amount: i64 },
+    AllEarn { amount_unit: i64 },
+    PromptLuckTest,
+    PromptFinancialCrisis { cost: i64 },
+    Educate,
+    MedicalCare { free: bool },
+    Concert { price: i64 },
+    GetRandomChanceCard,
+    PromptTicket { kind: String },
+    Log { message: String },
+    /// 알 수 없는 type은 `Log`와 같이 `message`를 기록
+    #[serde(other)]
+    Other,
+}
+
+/// 찬스 카드 스크립트(`script_chance_action`)가 반환하는 행동
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum ChanceAction {
+    Earn { amount: i64 },
+    Earthquake,
+    GoToJail,
+    GoToHospital,
+    GoToUniversity,
+    GetTicket { kind: String },
+    TwistOfFate { dice_a: usize, dice_b: usize },
+    PayTo(Payment),
+    WarpToPosition { position: u32 },
+    TravelToPosition { position: u32 },
+    /// 문자열이 아닌 항목은 무시
+    DestructOnePerEach { targets: Vec<serde_json::Value> },
+    Pandemic,
+    FreeConstruction { target: String },
+    Catastrophe,
+    #[serde(rename = "NOP")]
+    Nop,
+    GoToPayElectricityFee { using_ticket: bool },
+    GraduateNow,
+    PropertySwap { to_get: String, to_give: String },
+    /// 알 수 없는 type은 아무 일도 하지 않음
+    #[serde(other)]
+    Other,
+}
+
+/// 한 바퀴 스크립트(`script_cycle`)가 반환하는 정산 결과
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CycleResult {
+    pub new_government_income: i64,
+    pub remaining_salary: i64,
+    pub basic_income: i64,
+}
+
+#[derive(Deserialize)]
+struct LogMessage {
+    message: String,
+}
+
+fn from_map<T: DeserializeOwned>(map: &Map, script: &str) -> Result<T, GameError> {
+    let action_type = match map.get("type") {
+        Some(value) => value.clone().into_string().map_err(|t| GameError::InvalidScriptResult(format!("{} script returned 'type' of type '{}', expected a string.", script, t)))?,
+        None => return Err(GameError::InvalidScriptResult(format!("{} script returned a map without 'type'.", script))),
+    };
+    rhai::serde::from_dynamic(&Dynamic::from_map(map.clone())).map_err(|e| GameError::InvalidScriptResult(format!("Invalid '{}' action from {} script: {}", action_type, script, e)))
+}
+
+impl TurnAction {
+    pub(crate) fn from_map(map: &Map) -> Result<Self, GameError> {
+        match from_map(map, "Turn")? {
+            Self::Other => {
+                let LogMessage { message } = from_map(map, "Turn")?;
+                Ok(Self::Log { message })
+            },
+            action => Ok(action)
+        }
+    }
+}
+
+impl ChanceAction {
+    pub(crate) fn from_map(map: &Map) -> Result<Self, GameError> {
+        from_map(map, "Chance")
+    }
+}
+
+impl CycleResult {
+    pub(crate) fn from_map(map: &Map) -> Result<Self, GameError> {
+        rhai::serde::from_dynamic(&Dynamic::from_map(map.clone())).map_err(|e| GameError::InvalidScriptResult(format!("Invalid result from Cycle script: {}", e)))
+    }
+}
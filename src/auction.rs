@@ -0,0 +1,195 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, GameEngine, GameError, GameEvent, GameSituation, Ownership, ReplayStep};
+
+/// 진행 중인 경매
+///
+/// 파산하지 않은 모든 플레이어가 참여하며, 누구든 최고가보다 높게 부르거나 포기할 수 있다.
+/// 최고 입찰자를 뺀 모두가 포기하면 낙찰된다.
+/// 호스트는 제한 시간이 지나면 `close_auction`으로 경매를 끝낼 수 있다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Auction {
+    pub tile_name: String,
+    /// 아직 포기하지 않은 플레이어
+    pub bidders: Vec<u32>,
+    pub highest_bidder: Option<u32>,
+    pub highest_bid: i64,
+    /// 다음 입찰이 넘어야 하는 최소 금액
+    pub min_next_bid: i64,
+}
+
+impl GameEngine {
+    /// 룰셋에서 경매를 켰다면(`AUCTION_ENABLED`, 기본 0) 경매를 시작
+    ///
+    /// 첫 입찰은 `AUCTION_MIN_BID`(기본 1) 이상, 이후 입찰은 최고가보다
+    /// `AUCTION_BID_INCREMENT`(기본 1) 이상 높아야 한다.
+    pub(crate) fn start_auction(&mut self, tile_name: &str) -> bool {
+        if self.const_or("AUCTION_ENABLED", 0) == 0 || self.state.properties.contains_key(tile_name) {
+            return false;
+        }
+        let bidders = self.state.players.iter().filter(|player| !player.bankrupt).map(|player| player.id).collect::<Vec<_>>();
+        self.emit(GameEvent::AuctionStarted { tile_name: tile_name.into() });
+        self.state.auction = Some(Auction {
+            tile_name: tile_name.into(),
+            bidders,
+            highest_bidder: None,
+            highest_bid: 0,
+            min_next_bid: self.const_or("AUCTION_MIN_BID", 1) as i64,
+        });
+        self.now = GameSituation::PendingAuction;
+        true
+    }
+
+    fn auction_mut(&mut self) -> Result<&mut Auction, GameError> {
+        self.state.auction.as_mut().ok_or_else(|| GameError::InvalidBid("There is no auction in progress.".into()))
+    }
+
+    /// 최고 입찰자에게 낙찰하고 턴을 마저 진행
+    ///
+    /// 낙찰자는 건물 없이 땅만 소유한다.
+    /// 입찰한 뒤 돈을 써서 최고가를 낼 수 없게 되었다면 그 입찰을 무르고 유찰한다.
+    fn settle_auction(&mut self) {
+        let Some(auction) = self.state.auction.take() else {
+            return;
+        };
+        let winner = auction.highest_bidder.and_then(|winner| self.state.players.iter_mut().find(|player| player.id == winner));
+        match winner {
+            Some(winner_mut) if winner_mut.money >= auction.highest_bid => {
+                winner_mut.money -= auction.highest_bid;
+                let winner = winner_mut.id;
+                self.state.properties.insert(auction.tile_name.clone(), Ownership { owner_id: winner, buildings: 0, mortgaged: false });
+                self.emit(GameEvent::AuctionWon { player_id: winner, tile_name: auction.tile_name, amount: auction.highest_bid });
+            },
+            winner => {
+                if let Some(winner_mut) = winner {
+                    let player_id = winner_mut.id;
+                    self.emit(GameEvent::BidDropped { player_id, tile_name: auction.tile_name.clone(), amount: auction.highest_bid });
+                }
+                self.emit(GameEvent::AuctionUnsold { tile_name: auction.tile_name });
+            }
+        }
+        self.now = GameSituation::EndTurn;
+    }
+
+    /// 최고 입찰자만 남았거나 아무도 남지 않았다면 경매를 끝냄
+    fn settle_auction_if_decided(&mut self) {
+        if let Some(auction) = &self.state.auction {
+            let decided = match auction.highest_bidder {
+                Some(leader) => auction.bidders.iter().all(|&pid| pid == leader),
+                None => auction.bidders.is_empty(),
+            };
+            if decided {
+                self.settle_auction();
+            }
+        }
+    }
+
+    /// 진행 중인 경매
+    pub fn auction(&self) -> Option<&Auction> {
+        self.state.auction.as_ref()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// `player_id`가 `amount`를 부름 (가진 현금보다 많이 부를 수 없음)
+    pub fn place_bid(&mut self, player_id: u32, amount: i64) -> Result<(), GameError> {
+        self.expect(Command::PlaceBid)?;
+        self.record(ReplayStep::PlaceBid { player_id, amount }, |game| {
+            let money = game.state.players.iter().find(|player| player.id == player_id).ok_or(GameError::UnknownPlayer(player_id))?.money;
+            let increment = game.const_or("AUCTION_BID_INCREMENT", 1) as i64;
+            let auction = game.auction_mut()?;
+            if !auction.bidders.contains(&player_id) {
+                return Err(GameError::InvalidBid(format!("Player {} is not bidding on '{}'.", player_id, auction.tile_name)));
+            }
+            if amount < auction.min_next_bid {
+                return Err(GameError::InvalidBid(format!("The bid must be at least ${}.", auction.min_next_bid)));
+            }
+            if money < amount {
+                return Err(GameError::InsufficientFunds { required: amount, available: money });
+            }
+            auction.highest_bidder = Some(player_id);
+            auction.highest_bid = amount;
+            auction.min_next_bid = amount + increment.max(1);
+            let tile_name = auction.tile_name.clone();
+            game.emit(GameEvent::BidPlaced { player_id, tile_name, amount });
+            game.settle_auction_if_decided();
+            Ok(())
+        })
+    }
+
+    /// 경매에서 빠짐 (최고 입찰자는 빠질 수 없음)
+    pub fn pass_bid(&mut self, player_id: u32) -> Result<(), GameError> {
+        self.expect(Command::PassBid)?;
+        self.record(ReplayStep::PassBid { player_id }, |game| {
+            let auction = game.auction_mut()?;
+            if auction.highest_bidder == Some(player_id) {
+                return Err(GameError::InvalidBid(format!("Player {} holds the highest bid.", player_id)));
+            }
+            let Some(index) = auction.bidders.iter().position(|&pid| pid == player_id) else {
+                return Err(GameError::InvalidBid(format!("Player {} is not bidding on '{}'.", player_id, auction.tile_name)));
+            };
+            auction.bidders.remove(index);
+            game.emit(GameEvent::BidPassed { player_id });
+            game.settle_auction_if_decided();
+            Ok(())
+        })
+    }
+
+    /// 제한 시간이 지났을 때 현재 최고가로 경매를 끝냄
+    pub fn close_auction(&mut self) -> Result<(), GameError> {
+        self.expect(Command::CloseAuction)?;
+        self.record(ReplayStep::CloseAuction, |game| {
+            game.settle_auction();
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 진행 중인 경매 (`Auction`의 JSON, 없으면 `null`)
+    pub fn get_auction(&self) -> String {
+        serde_json::to_string(&self.auction()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, GameEvent, GameSituation};
+
+    #[test]
+    fn auction_is_won_by_the_last_bidder_standing() {
+        let mut game = testing::game(&[("AUCTION_ENABLED", 1)]);
+        assert!(game.start_auction("Busan"));
+        game.place_bid(1, 150_000).unwrap();
+        game.pass_bid(2).unwrap();
+
+        assert!(game.auction().is_none());
+        assert_eq!(game.now, GameSituation::EndTurn);
+        assert_eq!(game.state.properties["Busan"].owner_id, 1);
+        assert_eq!(game.state.players[0].money, 850_000);
+        assert_eq!(game.state.events.last(), Some(&GameEvent::AuctionWon { player_id: 1, tile_name: "Busan".into(), amount: 150_000 }));
+    }
+
+    #[test]
+    fn bid_is_dropped_when_the_winner_can_no_longer_pay() {
+        let mut game = testing::game(&[("AUCTION_ENABLED", 1)]);
+        assert!(game.start_auction("Busan"));
+        game.place_bid(1, 900_000).unwrap();
+        game.state.players[0].money = 100_000;
+        game.close_auction().unwrap();
+
+        assert!(!game.state.properties.contains_key("Busan"));
+        assert_eq!(game.state.players[0].money, 100_000);
+        assert_eq!(game.now, GameSituation::EndTurn);
+        let events = &game.state.events[game.state.events.len() - 2..];
+        assert_eq!(events, [
+            GameEvent::BidDropped { player_id: 1, tile_name: "Busan".into(), amount: 900_000 },
+            GameEvent::AuctionUnsold { tile_name: "Busan".into() },
+        ]);
+    }
+}
@@ -0,0 +1,365 @@
+//! 웹 프론트엔드 없이 터미널에서 룰셋을 시험하는 게임 실행기
+//!
+//! ```text
+//! jubilee-cli <board.json> <chance_cards.json> <consts.json> <action.rhai> <cycle.rhai> <chance.rhai> [options]
+//! ```
+//!
+//! 명령은 `GameEngine`의 메서드 이름을 그대로 쓰며, 매 명령 뒤에 새 로그와 플레이어 상태를 출력한다.
+
+use std::{env, fs, io::{self, BufRead, Write}, process::ExitCode};
+
+use jubilee_engine::{GameEngine, GameSituation, Scripts, SeatStrategy, TicketCount, TradeOffer, UndoPolicy};
+
+const USAGE: &str = "\
+Usage: jubilee-cli <board.json> <chance_cards.json> <consts.json> <action.rhai> <cycle.rhai> <chance.rhai> [options]
+
+Options:
+  --players <n>         number of players (default 2)
+  --money <amount>      initial money of each player (default 3000000)
+  --salary <amount>     salary paid on each cycle (default 200000)
+  --building-cost <n>   default building cost (default 100000)
+  --seed <n>            random seed (default: random)
+  --locale <locale>     log locale, en or ko (default en)
+  --bot <player>:<strategy>
+                        let a bot play the seat: random, greedy, conservative
+                        or a .rhai strategy script (repeatable)";
+
+const HELP: &str = "\
+Game commands (only the legal ones are accepted, see 'Legal:'):
+  roll_dice                          roll and move (or try to jailbreak while in jail)
+  run_turn_script <a> <b>            move with the given dice (only when the dice are not seeded)
+  try_to_jailbreak_by_dices <a> <b>  try to jailbreak with the given dice (likewise)
+  try_to_jailbreak_by_money | give_up_jailbreak
+  accept_purchase | decline_purchase
+  use_ticket [kind]                  FreeHospital, FreeProperty, DoubleLotto, NoTax, ReleaseFromJail, Bonus
+  luck_test [double]
+  stop_luck_test
+  get_random_chance_card | check_chance_card [payload_json]
+  borrow_money <amount> [player] | repay_loan <loan_id> <amount> [player]
+  sell_building <tile> | mortgage_property <tile> | redeem_mortgage <tile>
+  resolve_financial_crisis | declare_bankruptcy
+  propose_trade <to> <offer_json> | accept_trade | reject_trade | counter_trade <offer_json>
+  place_bid <player> <amount> | pass_bid <player> | close_auction
+  end_turn
+Other commands:
+  state | decision | ranking | log [n] | undo | redo
+  save <file> | load <file> | replay <file> | help | quit";
+
+struct Config {
+    board_json: String,
+    chance_cards_json: String,
+    consts_json: String,
+    scripts: Scripts,
+    players: usize,
+    money: i64,
+    salary: i64,
+    building_cost: i64,
+    seed: Option<u64>,
+    locale: Option<String>,
+    bots: Vec<(u32, SeatStrategy)>,
+}
+
+enum Flow {
+    /// 상태가 바뀌었을 수 있으므로 다시 출력
+    Continue,
+    /// 출력할 것이 없음
+    Quiet,
+    Quit,
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: Option<&str>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing {}.", name))?;
+    value.parse().map_err(|_| format!("Invalid {} '{}'.", name, value))
+}
+
+fn parse_config(args: &[String]) -> Result<Config, String> {
+    let (files, options) = args.split_at(args.len().min(6));
+    let [board, chance_cards, consts, action, cycle, chance] = files else {
+        return Err(USAGE.into());
+    };
+    let mut config = Config {
+        board_json: read_file(board)?,
+        chance_cards_json: read_file(chance_cards)?,
+        consts_json: read_file(consts)?,
+        scripts: Scripts { action: read_file(action)?, cycle: read_file(cycle)?, chance: read_file(chance)? },
+        players: 2,
+        money: 3_000_000,
+        salary: 200_000,
+        building_cost: 100_000,
+        seed: None,
+        locale: None,
+        bots: Vec::new(),
+    };
+    let mut options = options.iter().map(String::as_str);
+    while let Some(option) = options.next() {
+        match option {
+            "--players" => config.players = parse_number("player count", options.next())?,
+            "--money" => config.money = parse_number("money", options.next())?,
+            "--salary" => config.salary = parse_number("salary", options.next())?,
+            "--building-cost" => config.building_cost = parse_number("building cost", options.next())?,
+            "--seed" => config.seed = Some(parse_number("seed", options.next())?),
+            "--locale" => config.locale = Some(options.next().ok_or("Missing locale.")?.into()),
+            "--bot" => {
+                let bot = options.next().ok_or("Missing bot.")?;
+                let (player, strategy) = bot.split_once(':').ok_or_else(|| format!("Invalid bot '{}'.", bot))?;
+                let strategy = if strategy.ends_with(".rhai") {
+                    SeatStrategy::Script { name: strategy.into(), source: read_file(strategy)? }
+                } else {
+                    SeatStrategy::Builtin(strategy.parse().map_err(|e: jubilee_engine::GameError| format!("[{}] {}", e.code(), e))?)
+                };
+                config.bots.push((parse_number("player", Some(player))?, strategy));
+            },
+            _ => return Err(format!("Unknown option '{}'.\n\n{}", option, USAGE)),
+        }
+    }
+    Ok(config)
+}
+
+fn print_status(game: &GameEngine, log_printed: &mut usize) {
+    let state = game.state();
+    for line in state.log().iter().skip(*log_printed) {
+        println!("  {}", line);
+    }
+    *log_printed = state.log().len();
+
+    let board = state.board();
+    println!("--- Turn {}, Player {}: {:?}", state.turn_count() + 1, state.current_player().id, game.get_situation());
+    for player in state.players() {
+        let mut line = format!("  P{} ${} at {} ({})", player.id, player.money, player.position, board[player.position as usize].name);
+        let loans = player.remaining_loans.iter().map(|loan| loan.amount).sum::<i64>();
+        if loans > 0 {
+            line += &format!(", loans ${}", loans);
+        }
+        if player.remaining_jail_turns > 0 {
+            line += &format!(", jailed for {} turns", player.remaining_jail_turns);
+        }
+        if player.bankrupt {
+            line += ", bankrupt";
+        }
+        println!("{}", line);
+    }
+    if game.get_situation() == GameSituation::EndGame {
+        print_ranking(game);
+    } else {
+        println!("Legal: {}", game.get_legal_actions().join(", "));
+    }
+}
+
+fn print_properties(game: &GameEngine) {
+    let state = game.state();
+    for (position, tile) in state.board().iter().enumerate() {
+        let owner = match state.properties().get(&tile.name) {
+            Some(ownership) => format!(" - P{}, {} buildings{}", ownership.owner_id, ownership.buildings, if ownership.mortgaged { ", mortgaged" } else { "" }),
+            None if tile.price > 0 => format!(" - ${}", tile.price),
+            None => String::new(),
+        };
+        println!("  {:>2} {} [{}]{}", position, tile.name, tile.tile_type, owner);
+    }
+}
+
+fn print_ranking(game: &GameEngine) {
+    for entry in game.ranking() {
+        println!("  #{} P{} net worth ${}{}", entry.rank, entry.player_id, entry.net_worth, if entry.bankrupt { " (bankrupt)" } else { "" });
+    }
+}
+
+/// 사람의 결정을 기다리거나 게임이 끝날 때까지 봇을 움직이고, 봇이 움직였는지 반환
+fn run_bots(game: &mut GameEngine, scripts: &Scripts) -> Result<bool, String> {
+    let mut moved = false;
+    while let Some(player_id) = game.bot_to_act() {
+        let action = game.step_bot(scripts).map_err(|e| format!("[{}] {}", e.code(), e))?;
+        if let Some(action) = action {
+            println!("  P{} (bot): {}", player_id, serde_json::to_string(&action).unwrap());
+            moved = true;
+        }
+    }
+    Ok(moved)
+}
+
+/// 한 줄의 명령을 실행
+fn execute(game: &mut GameEngine, scripts: &Scripts, line: &str) -> Result<Flow, String> {
+    let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let mut args = rest.split_whitespace();
+    let current_id = game.state().current_player().id;
+    let engine_error = |e: jubilee_engine::GameError| format!("[{}] {}", e.code(), e);
+
+    match command {
+        "" => return Ok(Flow::Quiet),
+        "help" | "?" => {
+            println!("{}", HELP);
+            return Ok(Flow::Quiet);
+        },
+        "quit" | "exit" => return Ok(Flow::Quit),
+        "state" => {
+            print_properties(game);
+            return Ok(Flow::Quiet);
+        },
+        "decision" => {
+            println!("{}", serde_json::to_string_pretty(&game.pending_decision()).unwrap());
+            return Ok(Flow::Quiet);
+        },
+        "ranking" => {
+            print_ranking(game);
+            return Ok(Flow::Quiet);
+        },
+        "log" => {
+            let count = if rest.is_empty() { 20 } else { parse_number("line count", Some(rest))? };
+            let log = game.state().log();
+            for line in &log[log.len().saturating_sub(count)..] {
+                println!("  {}", line);
+            }
+            return Ok(Flow::Quiet);
+        },
+        "save" => {
+            let json = serde_json::to_string_pretty(&game.save_file()).unwrap();
+            fs::write(rest, json).map_err(|e| format!("Cannot write '{}': {}", rest, e))?;
+            return Ok(Flow::Quiet);
+        },
+        "load" => game.load_state_from_json(&read_file(rest)?).map_err(engine_error)?,
+        "replay" => {
+            let json = serde_json::to_string_pretty(&game.replay_document()).unwrap();
+            fs::write(rest, json).map_err(|e| format!("Cannot write '{}': {}", rest, e))?;
+            return Ok(Flow::Quiet);
+        },
+        "undo" => game.undo().map_err(engine_error)?,
+        "redo" => game.redo().map_err(engine_error)?,
+
+        "roll_dice" => {
+            let dices = if matches!(game.get_situation(), GameSituation::PendingTryToJailbreakResponse | GameSituation::PendingJailbreakByDicesResponse) {
+                game.roll_and_try_to_jailbreak()
+            } else {
+                game.roll_and_run_turn_script(&scripts.action, &scripts.cycle)
+            }.map_err(engine_error)?;
+            println!("Rolled ({}, {})", dices.first(), dices.second());
+        },
+        "run_turn_script" | "try_to_jailbreak_by_dices" => {
+            let dices = jubilee_engine::DicePair::new(parse_number("die", args.next())?, parse_number("die", args.next())?).map_err(engine_error)?;
+            if command == "run_turn_script" {
+                game.run_turn_script(&scripts.action, dices, &scripts.cycle)
+            } else {
+                game.try_to_jailbreak_by_dices(dices)
+            }.map_err(engine_error)?
+        },
+        "try_to_jailbreak_by_money" => game.try_to_jailbreak_by_money().map_err(engine_error)?,
+        "give_up_jailbreak" => game.give_up_jailbreak().map_err(engine_error)?,
+        "accept_purchase" => game.accept_purchase().map_err(engine_error)?,
+        "decline_purchase" => game.decline_purchase().map_err(engine_error)?,
+        "use_ticket" => {
+            let tickets = if rest.is_empty() { TicketCount::zero() } else { TicketCount::get_one_ticket(rest) };
+            game.use_ticket(tickets, &scripts.action, &scripts.cycle).map_err(engine_error)?
+        },
+        "luck_test" => game.luck_test(rest == "double").map_err(engine_error)?,
+        "stop_luck_test" => game.stop_luck_test().map_err(engine_error)?,
+        "get_random_chance_card" => game.get_random_chance_card().map_err(engine_error)?,
+        "check_chance_card" => {
+            let payload = (!rest.is_empty()).then(|| rest.to_string());
+            game.check_chance_card(&scripts.chance, &scripts.cycle, payload).map_err(engine_error)?
+        },
+        "borrow_money" => {
+            let amount = parse_number("amount", args.next())?;
+            let player_id = args.next().map_or(Ok(current_id), |id| parse_number("player", Some(id)))?;
+            game.borrow_money(player_id, amount).map_err(engine_error)?
+        },
+        "repay_loan" => {
+            let loan_id = parse_number("loan id", args.next())?;
+            let amount = parse_number("amount", args.next())?;
+            let player_id = args.next().map_or(Ok(current_id), |id| parse_number("player", Some(id)))?;
+            game.repay_loan(player_id, loan_id, amount).map_err(engine_error)?
+        },
+        "sell_building" => game.sell_building(rest).map_err(engine_error)?,
+        "mortgage_property" => game.mortgage_property(rest).map_err(engine_error)?,
+        "redeem_mortgage" => game.redeem_mortgage(rest).map_err(engine_error)?,
+        "resolve_financial_crisis" => game.resolve_financial_crisis().map_err(engine_error)?,
+        "declare_bankruptcy" => game.declare_bankruptcy().map_err(engine_error)?,
+        "propose_trade" => {
+            let (to, offer_json) = rest.split_once(char::is_whitespace).ok_or("Usage: propose_trade <to> <offer_json>")?;
+            let offer: TradeOffer = serde_json::from_str(offer_json).map_err(|e| format!("Invalid offer: {}", e))?;
+            game.propose_trade(current_id, parse_number("player", Some(to))?, offer).map_err(engine_error)?
+        },
+        "accept_trade" => game.accept_trade().map_err(engine_error)?,
+        "reject_trade" => game.reject_trade().map_err(engine_error)?,
+        "counter_trade" => {
+            let offer: TradeOffer = serde_json::from_str(rest).map_err(|e| format!("Invalid offer: {}", e))?;
+            game.counter_trade(offer).map_err(engine_error)?
+        },
+        "place_bid" => {
+            let player_id = parse_number("player", args.next())?;
+            game.place_bid(player_id, parse_number("amount", args.next())?).map_err(engine_error)?
+        },
+        "pass_bid" => game.pass_bid(parse_number("player", args.next())?).map_err(engine_error)?,
+        "close_auction" => game.close_auction().map_err(engine_error)?,
+        "end_turn" => game.end_turn().map_err(engine_error)?,
+        _ => return Err(format!("Unknown command '{}'. Type 'help' for the list of commands.", command)),
+    }
+    Ok(Flow::Continue)
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let config = match parse_config(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut game = match GameEngine::new(&config.board_json, &config.chance_cards_json, &config.consts_json, config.players, config.money, config.salary, config.building_cost, config.seed) {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("[{}] {}", e.code(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    // 한 터미널에서 번갈아 하므로 되돌리기를 켬
+    game.set_undo_policy(UndoPolicy::UntilRandomness);
+    if let Some(locale) = &config.locale
+        && let Err(e) = game.set_locale(locale) {
+        eprintln!("[{}] {}", e.code(), e);
+        return ExitCode::FAILURE;
+    }
+
+    for (player_id, strategy) in config.bots.iter() {
+        if let Err(e) = strategy.assign(&mut game, *player_id, config.seed.unwrap_or(0).wrapping_add(*player_id as u64)) {
+            eprintln!("[{}] {}", e.code(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!("Seed {}. Type 'help' for the list of commands.", game.get_seed());
+    let mut log_printed = 0;
+    if let Err(e) = run_bots(&mut game, &config.scripts) {
+        eprintln!("{}", e);
+    }
+    print_status(&game, &mut log_printed);
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("P{}> ", game.state().current_player().id);
+        _ = io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        match execute(&mut game, &config.scripts, line.trim()) {
+            Ok(Flow::Continue) => {
+                // 되돌리거나 불러와서 로그가 줄었다면 처음부터 다시 출력하지 않음
+                log_printed = log_printed.min(game.state().log().len());
+                if let Err(e) = run_bots(&mut game, &config.scripts) {
+                    eprintln!("{}", e);
+                }
+                print_status(&game, &mut log_printed);
+            },
+            Ok(Flow::Quiet) => {},
+            Ok(Flow::Quit) => break,
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    ExitCode::SUCCESS
+}
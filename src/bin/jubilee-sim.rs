@@ -0,0 +1,129 @@
+//! 룰셋으로 게임을 여러 판 돌려 밸런스 통계를 내는 시뮬레이터
+//!
+//! ```text
+//! jubilee-sim <board.json> <chance_cards.json> <consts.json> <action.rhai> <cycle.rhai> <chance.rhai> [options]
+//! ```
+//!
+//! 보고서는 `SimulationReport`의 JSON 또는 `table,key,metric,value` 형식의 CSV로 출력한다.
+
+use std::{env, fs, process::ExitCode};
+
+use jubilee_engine::{simulate, GameError, Scripts, SeatStrategy, SimulationConfig};
+
+const USAGE: &str = "\
+Usage: jubilee-sim <board.json> <chance_cards.json> <consts.json> <action.rhai> <cycle.rhai> <chance.rhai> [options]
+
+Options:
+  --games <n>           number of games (default 1000)
+  --seed <n>            seed of the first game, game i uses seed + i (default 0)
+  --max-turns <n>       games still running after this many turns are unfinished (default 1000)
+  --threads <n>         worker threads (default: available cores)
+  --players <n>         number of players (default 2)
+  --strategies <list>   comma-separated bot strategy of each seat, repeated over the seats:
+                        random, greedy, conservative or a .rhai strategy script (default greedy)
+  --money <amount>      initial money of each player (default 3000000)
+  --salary <amount>     salary paid on each cycle (default 200000)
+  --building-cost <n>   default building cost (default 100000)
+  --format <format>     json or csv (default json)
+  --output <file>       write the report to a file instead of stdout";
+
+enum Format {
+    Json,
+    Csv,
+}
+
+struct Options {
+    config: SimulationConfig,
+    format: Format,
+    output: Option<String>,
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: Option<&str>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing {}.", name))?;
+    value.parse().map_err(|_| format!("Invalid {} '{}'.", name, value))
+}
+
+/// 내장 전략 이름, 또는 `.rhai`로 끝나면 전략 스크립트 파일
+fn parse_strategy(name: &str) -> Result<SeatStrategy, String> {
+    if name.ends_with(".rhai") {
+        Ok(SeatStrategy::Script { name: name.into(), source: read_file(name)? })
+    } else {
+        name.parse().map(SeatStrategy::Builtin).map_err(|e: GameError| format!("[{}] {}", e.code(), e))
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let (files, options) = args.split_at(args.len().min(6));
+    let [board, chance_cards, consts, action, cycle, chance] = files else {
+        return Err(USAGE.into());
+    };
+    let scripts = Scripts { action: read_file(action)?, cycle: read_file(cycle)?, chance: read_file(chance)? };
+    let config = SimulationConfig::from_json(&read_file(board)?, &read_file(chance_cards)?, &read_file(consts)?, scripts)
+        .map_err(|e| format!("[{}] {}", e.code(), e))?;
+    let mut parsed = Options { config, format: Format::Json, output: None };
+    let config = &mut parsed.config;
+    let mut options = options.iter().map(String::as_str);
+    while let Some(option) = options.next() {
+        match option {
+            "--games" => config.games = parse_number("game count", options.next())?,
+            "--seed" => config.seed = parse_number("seed", options.next())?,
+            "--max-turns" => config.max_turns = parse_number("turn count", options.next())?,
+            "--threads" => config.threads = parse_number("thread count", options.next())?,
+            "--players" => config.players_count = parse_number("player count", options.next())?,
+            "--strategies" => config.strategies = options.next().ok_or("Missing strategies.")?.split(',')
+                .map(|name| parse_strategy(name.trim()))
+                .collect::<Result<_, _>>()?,
+            "--money" => config.initial_money = parse_number("money", options.next())?,
+            "--salary" => config.salary = parse_number("salary", options.next())?,
+            "--building-cost" => config.building_cost = parse_number("building cost", options.next())?,
+            "--format" => parsed.format = match options.next() {
+                Some("json") => Format::Json,
+                Some("csv") => Format::Csv,
+                format => return Err(format!("Invalid format '{}'.", format.unwrap_or_default())),
+            },
+            "--output" => parsed.output = Some(options.next().ok_or("Missing output file.")?.into()),
+            _ => return Err(format!("Unknown option '{}'.\n\n{}", option, USAGE)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let report = match simulate(&options.config) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("[{}] {}", e.code(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for failed in report.failed.iter() {
+        eprintln!("Game with seed {} stopped at turn {}: {}", failed.seed, failed.turn, failed.error);
+    }
+
+    let output = match options.format {
+        Format::Json => report.to_json() + "\n",
+        Format::Csv => report.to_csv(),
+    };
+    match &options.output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, output) {
+                eprintln!("Cannot write '{}': {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => print!("{}", output),
+    }
+    ExitCode::SUCCESS
+}
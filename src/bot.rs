@@ -0,0 +1,535 @@
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Auction, ChanceAction, ChanceOption, GameEngine, GameError, GameRng, GameSituation, LiquidationOption, PendingDecision, Scripts, TicketCount, TradeSide};
+
+/// 봇이 내린 결정 (엔진 명령 하나에 대응)
+///
+/// 대출과 입찰처럼 플레이어 id가 필요한 명령은 봇이 조종하는 플레이어로 실행한다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BotAction {
+    /// 주사위를 굴려 움직임 (감옥에서는 주사위로 탈옥을 시도, 이미 굴렸다면 그 주사위를 씀)
+    RollDice,
+    AcceptPurchase,
+    DeclinePurchase,
+    UseTicket { tickets: TicketCount },
+    /// 행운 시험을 시작하거나 계속함
+    LuckTest { double_lotto: bool },
+    /// 행운 시험을 그만두고 쌓인 상금을 받음
+    StopLuckTest,
+    TryToJailbreakByMoney,
+    GiveUpJailbreak,
+    GetRandomChanceCard,
+    /// `payload_json`은 찬스 카드 스크립트에 넘길 JSON
+    CheckChanceCard { payload_json: Option<String> },
+    BorrowMoney { amount: i64 },
+    RepayLoan { loan_id: u32, amount: i64 },
+    SellBuilding { tile_name: String },
+    MortgageProperty { tile_name: String },
+    RedeemMortgage { tile_name: String },
+    ResolveFinancialCrisis,
+    DeclareBankruptcy,
+    AcceptTrade,
+    RejectTrade,
+    PlaceBid { amount: i64 },
+    PassBid,
+    EndTurn,
+}
+
+/// 봇 플레이어의 전략
+///
+/// `decision`은 `player_id`가 내려야 하는 결정이며, `game`으로 `GameState`(`state()`)와
+/// 순자산, 처분 방법 같은 계산 결과를 읽을 수 있다.
+pub trait PlayerStrategy {
+    fn decide(&mut self, player_id: u32, decision: &PendingDecision, game: &GameEngine) -> Result<BotAction, GameError>;
+}
+
+/// 엔진에 내장된 전략
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinStrategy {
+    Random,
+    Greedy,
+    Conservative,
+}
+
+impl BuiltinStrategy {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Greedy => "greedy",
+            Self::Conservative => "conservative",
+        }
+    }
+
+    /// `seed`는 무작위 전략만 사용
+    pub fn build(self, seed: u64) -> Box<dyn PlayerStrategy> {
+        match self {
+            Self::Random => Box::new(RandomStrategy::new(seed)),
+            Self::Greedy => Box::new(GreedyStrategy),
+            Self::Conservative => Box::new(ConservativeStrategy),
+        }
+    }
+}
+
+impl FromStr for BuiltinStrategy {
+    type Err = GameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        [Self::Random, Self::Greedy, Self::Conservative].into_iter()
+            .find(|strategy| strategy.name() == name)
+            .ok_or_else(|| GameError::UnknownStrategy(name.into()))
+    }
+}
+
+/// 감옥에서 돈을 내고 나오는 비용
+fn jail_fee(game: &GameEngine) -> Option<i64> {
+    game.state.board.iter().find(|tile| tile.tile_type == "Jail").map(|tile| tile.amount)
+}
+
+/// 땅값과 건물 값으로 어림한 부동산의 가치
+fn property_value(game: &GameEngine, name: &str) -> i64 {
+    let land = game.tile_by_name(name).map(|tile| tile.price).unwrap_or(0);
+    let buildings = game.state.properties.get(name).map(|ownership| ownership.buildings).unwrap_or(0);
+    land + game.buildings_value(name, buildings)
+}
+
+/// 땅값과 건물 값, 티켓 값(`TICKET_VALUE`)으로 어림한 거래 한쪽의 가치
+fn trade_side_value(game: &GameEngine, side: &TradeSide) -> i64 {
+    let properties = side.properties.iter().map(|name| property_value(game, name)).sum::<i64>();
+    let tickets = side.tickets;
+    let tickets_count = tickets.free_hospital + tickets.free_property + tickets.double_lotto + tickets.no_tax + tickets.release_from_jail + tickets.bonus;
+    side.money + properties + tickets_count as i64 * game.const_or("TICKET_VALUE", 0) as i64
+}
+
+/// 재정 위기를 벗어나는 다음 한 걸음
+///
+/// `prefer_loan`이면 부동산을 지키려고 긴급 대출부터 받고, 아니면 저당, 건물 매각, 대출 순으로 마련한다.
+/// 모두 처분해도 부족하면 파산한다.
+fn liquidate(game: &GameEngine, deficit: i64, prefer_loan: bool) -> BotAction {
+    if deficit == 0 {
+        return BotAction::ResolveFinancialCrisis;
+    }
+    let options = game.liquidation_options();
+    let loan = options.iter().find_map(|option| match option {
+        LiquidationOption::EmergencyLoan { amount } => Some(BotAction::BorrowMoney { amount: *amount }),
+        _ => None,
+    });
+    if prefer_loan && let Some(loan) = loan {
+        return loan;
+    }
+    let mortgage = options.iter().find_map(|option| match option {
+        LiquidationOption::Mortgage { tile_name, .. } => Some(BotAction::MortgageProperty { tile_name: tile_name.clone() }),
+        _ => None,
+    });
+    let sell = options.iter().find_map(|option| match option {
+        LiquidationOption::SellBuilding { tile_name, .. } => Some(BotAction::SellBuilding { tile_name: tile_name.clone() }),
+        _ => None,
+    });
+    if game.liquidation_value() < deficit && loan.is_none() {
+        return BotAction::DeclareBankruptcy;
+    }
+    mortgage.or(sell).or(loan).unwrap_or(BotAction::DeclareBankruptcy)
+}
+
+/// 찬스 카드 행동으로 `player_id`의 현금과 순자산이 바뀌는 정도를 어림한 값 (`(현금, 순자산)`)
+///
+/// 돈과 부동산에 직접 닿지 않는 행동은 0으로 본다.
+fn chance_value(game: &GameEngine, player_id: u32, action: &ChanceAction) -> (i64, i64) {
+    let owned = |name: &str| game.state.properties.get(name).filter(|ownership| ownership.owner_id == player_id);
+    match action {
+        ChanceAction::Earn { amount } => (*amount, *amount),
+        ChanceAction::PayTo(payment) => {
+            let to_player = payment.player_amount.filter(|_| payment.to_player_id.is_some_and(|pid| pid != player_id));
+            let paid = payment.gov_amount.unwrap_or(0) + payment.market_amount.unwrap_or(0) + to_player.unwrap_or(0);
+            (-paid, -paid)
+        },
+        ChanceAction::GoToPayElectricityFee { using_ticket } => {
+            let player = game.state.players.iter().find(|player| player.id == player_id);
+            let free = *using_ticket && player.is_some_and(|player| player.tickets_count.no_tax > 0);
+            let fee = if free { 0 } else { game.tile_by_name("Electricity").map(|tile| tile.amount).unwrap_or(0) };
+            (-fee, -fee)
+        },
+        ChanceAction::FreeConstruction { target } if owned(target).is_some() => {
+            (0, game.next_building_tier(target).map(|tier| tier.cost).unwrap_or(0))
+        },
+        ChanceAction::PropertySwap { to_get, to_give } => (0, property_value(game, to_get) - property_value(game, to_give)),
+        ChanceAction::DestructOnePerEach { targets } => {
+            let lost = targets.iter().filter_map(|target| target.as_str()).filter_map(|name| {
+                let buildings = owned(name)?.buildings;
+                game.is_top_building_destructible(name, buildings).then(|| game.buildings_value(name, buildings) - game.buildings_value(name, buildings - 1))
+            }).sum::<i64>();
+            (0, -lost)
+        },
+        _ => (0, 0),
+    }
+}
+
+/// `key`가 가장 큰 `payload`로 찬스 카드를 확인 (후보가 없으면 `payload` 없이, 같으면 앞의 것)
+///
+/// `key`는 `chance_value`의 `(현금, 순자산)`을 받으며, 미리 보지 못한 후보는 가장 나중에 고른다.
+fn pick_chance_option(game: &GameEngine, player_id: u32, options: &[ChanceOption], key: impl Fn((i64, i64)) -> (i64, i64)) -> BotAction {
+    let best = options.iter().rev().max_by_key(|option| option.preview.as_ref().map(|action| key(chance_value(game, player_id, action))));
+    BotAction::CheckChanceCard { payload_json: best.map(|option| option.payload_json.clone()) }
+}
+
+/// `limit`이 최소 입찰액 이상이면 `limit`을 한 번에 부르고, 아니면 포기
+///
+/// 조금씩 올려 부르면 입찰 단위(`AUCTION_BID_INCREMENT`)가 작을 때 봇끼리 끝없이 경쟁한다.
+fn bid_up_to(auction: &Auction, limit: i64) -> BotAction {
+    if auction.min_next_bid <= limit {
+        BotAction::PlaceBid { amount: limit }
+    } else {
+        BotAction::PassBid
+    }
+}
+
+fn auctioned_price(game: &GameEngine, auction: &Auction) -> i64 {
+    game.tile_by_name(&auction.tile_name).map(|tile| tile.price).unwrap_or(0)
+}
+
+fn money_of(game: &GameEngine, player_id: u32) -> i64 {
+    game.state.players.iter().find(|player| player.id == player_id).map(|player| player.money).unwrap_or(0)
+}
+
+/// 할 수 있는 행동 중 하나를 무작위로 고르는 전략 (시드가 같으면 같은 선택)
+pub struct RandomStrategy {
+    rng: GameRng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: GameRng::new(seed) }
+    }
+}
+
+impl PlayerStrategy for RandomStrategy {
+    fn decide(&mut self, player_id: u32, decision: &PendingDecision, game: &GameEngine) -> Result<BotAction, GameError> {
+        let money = money_of(game, player_id);
+        Ok(match decision {
+            PendingDecision::PendingRollResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingMoveResponse { .. } | PendingDecision::PendingJailbreakByDicesResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingBuyResponse { .. } => if self.rng.random_bool(0.5) { BotAction::AcceptPurchase } else { BotAction::DeclinePurchase },
+            PendingDecision::PendingFinancialCrisisResponse { deficit, .. } => liquidate(game, *deficit, self.rng.random_bool(0.5)),
+            PendingDecision::PendingLuckTestResponse { pot, .. } => {
+                if *pot > 0 && self.rng.random_bool(0.5) { BotAction::StopLuckTest } else { BotAction::LuckTest { double_lotto: false } }
+            },
+            PendingDecision::PendingUseTicketResponse { usable_tickets, .. } => {
+                BotAction::UseTicket { tickets: if self.rng.random_bool(0.5) { *usable_tickets } else { TicketCount::default() } }
+            },
+            PendingDecision::PendingTryToJailbreakResponse { .. } => {
+                let can_pay = jail_fee(game).is_some_and(|fee| money >= fee);
+                match self.rng.random_range(0..3) {
+                    0 if can_pay => BotAction::TryToJailbreakByMoney,
+                    1 => BotAction::GiveUpJailbreak,
+                    _ => BotAction::RollDice,
+                }
+            },
+            PendingDecision::PendingGetRandomChanceCardResponse { .. } => BotAction::GetRandomChanceCard,
+            PendingDecision::PendingCheckChanceCardResponse { options, .. } => {
+                let option = (!options.is_empty()).then(|| &options[self.rng.random_range(0..options.len())]);
+                BotAction::CheckChanceCard { payload_json: option.map(|option| option.payload_json.clone()) }
+            },
+            PendingDecision::PendingTradeResponse { .. } => if self.rng.random_bool(0.5) { BotAction::AcceptTrade } else { BotAction::RejectTrade },
+            PendingDecision::PendingAuction { auction: Some(auction), .. } => {
+                let limit = self.rng.random_range(0..=money.min(auctioned_price(game, auction)).max(0));
+                bid_up_to(auction, limit)
+            },
+            PendingDecision::PendingAuction { auction: None, .. } => BotAction::PassBid,
+            PendingDecision::InAction | PendingDecision::EndTurn { .. } | PendingDecision::EndGame => BotAction::EndTurn,
+        })
+    }
+}
+
+/// 살 수 있는 것은 모두 사고, 티켓은 모두 쓰며, 부동산을 지키려고 빚을 지는 전략
+///
+/// 경매에서는 땅값까지 부르고, 받는 쪽의 가치가 더 큰 거래만 받아들인다.
+/// 행운 시험은 상금이 `BOT_LUCK_TEST_TARGET`(기본 2000000)에 이를 때까지 계속하고,
+/// 찬스 카드는 순자산이 가장 많이 늘어나는 `payload`로 확인한다.
+pub struct GreedyStrategy;
+
+impl PlayerStrategy for GreedyStrategy {
+    fn decide(&mut self, player_id: u32, decision: &PendingDecision, game: &GameEngine) -> Result<BotAction, GameError> {
+        let money = money_of(game, player_id);
+        Ok(match decision {
+            PendingDecision::PendingRollResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingMoveResponse { .. } | PendingDecision::PendingJailbreakByDicesResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingBuyResponse { .. } => BotAction::AcceptPurchase,
+            PendingDecision::PendingFinancialCrisisResponse { deficit, .. } => liquidate(game, *deficit, true),
+            PendingDecision::PendingLuckTestResponse { pot, .. } => {
+                if *pot >= game.const_or("BOT_LUCK_TEST_TARGET", 2_000_000) as i64 { BotAction::StopLuckTest } else { BotAction::LuckTest { double_lotto: false } }
+            },
+            PendingDecision::PendingUseTicketResponse { usable_tickets, .. } => BotAction::UseTicket { tickets: *usable_tickets },
+            PendingDecision::PendingTryToJailbreakResponse { .. } => {
+                if jail_fee(game).is_some_and(|fee| money >= fee) { BotAction::TryToJailbreakByMoney } else { BotAction::RollDice }
+            },
+            PendingDecision::PendingGetRandomChanceCardResponse { .. } => BotAction::GetRandomChanceCard,
+            PendingDecision::PendingCheckChanceCardResponse { options, .. } => pick_chance_option(game, player_id, options, |(cash, net_worth)| (net_worth, cash)),
+            PendingDecision::PendingTradeResponse { trade: Some(trade), .. } => {
+                // 제안자가 주는 것(`give`)을 받고 바라는 것(`take`)을 내줌
+                if trade_side_value(game, &trade.offer.give) > trade_side_value(game, &trade.offer.take) { BotAction::AcceptTrade } else { BotAction::RejectTrade }
+            },
+            PendingDecision::PendingTradeResponse { trade: None, .. } => BotAction::RejectTrade,
+            PendingDecision::PendingAuction { auction: Some(auction), .. } => bid_up_to(auction, money.min(auctioned_price(game, auction))),
+            PendingDecision::PendingAuction { auction: None, .. } => BotAction::PassBid,
+            PendingDecision::InAction | PendingDecision::EndTurn { .. } | PendingDecision::EndGame => BotAction::EndTurn,
+        })
+    }
+}
+
+/// 현금을 `BOT_RESERVE`(기본 500000) 이상 남기는 전략
+///
+/// 남는 돈으로 대출과 저당부터 갚고, 거래는 거절하며, 경매에서는 땅값의 절반까지만 부른다.
+/// 재정 위기에는 긴급 대출을 마지막 수단으로 쓴다.
+/// 행운 시험은 상금이 생기면 바로 그만두고, 찬스 카드는 현금이 가장 많이 남는 `payload`로 확인한다.
+pub struct ConservativeStrategy;
+
+impl ConservativeStrategy {
+    /// 턴을 시작하거나 끝내기 전에 남는 돈으로 갚을 빚
+    fn repay(game: &GameEngine, player_id: u32, reserve: i64) -> Option<BotAction> {
+        let player = game.state.players.iter().find(|player| player.id == player_id)?;
+        let spare = player.money - reserve;
+        if let Some(loan) = player.remaining_loans.iter().find(|loan| loan.amount <= spare) {
+            return Some(BotAction::RepayLoan { loan_id: loan.id, amount: loan.amount });
+        }
+        let mut mortgaged = game.state.properties.iter().filter(|(_, ownership)| ownership.owner_id == player_id && ownership.mortgaged).map(|(name, _)| name).collect::<Vec<_>>();
+        mortgaged.sort();
+        mortgaged.into_iter().find(|name| {
+            game.mortgage_value(name).is_ok_and(|value| value + value / 10 <= spare)
+        }).map(|name| BotAction::RedeemMortgage { tile_name: name.clone() })
+    }
+}
+
+impl PlayerStrategy for ConservativeStrategy {
+    fn decide(&mut self, player_id: u32, decision: &PendingDecision, game: &GameEngine) -> Result<BotAction, GameError> {
+        let money = money_of(game, player_id);
+        let reserve = game.const_or("BOT_RESERVE", 500_000) as i64;
+        Ok(match decision {
+            PendingDecision::PendingRollResponse { .. } => Self::repay(game, player_id, reserve).unwrap_or(BotAction::RollDice),
+            PendingDecision::PendingMoveResponse { .. } | PendingDecision::PendingJailbreakByDicesResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingBuyResponse { land_price, building_cost, .. } => {
+                let price = land_price.unwrap_or(*building_cost);
+                if money - price >= reserve { BotAction::AcceptPurchase } else { BotAction::DeclinePurchase }
+            },
+            PendingDecision::PendingFinancialCrisisResponse { deficit, .. } => liquidate(game, *deficit, false),
+            PendingDecision::PendingLuckTestResponse { pot, .. } => if *pot > 0 { BotAction::StopLuckTest } else { BotAction::LuckTest { double_lotto: false } },
+            PendingDecision::PendingUseTicketResponse { usable_tickets, .. } => BotAction::UseTicket { tickets: *usable_tickets },
+            PendingDecision::PendingTryToJailbreakResponse { .. } => BotAction::RollDice,
+            PendingDecision::PendingGetRandomChanceCardResponse { .. } => BotAction::GetRandomChanceCard,
+            PendingDecision::PendingCheckChanceCardResponse { options, .. } => pick_chance_option(game, player_id, options, |value| value),
+            PendingDecision::PendingTradeResponse { .. } => BotAction::RejectTrade,
+            PendingDecision::PendingAuction { auction: Some(auction), .. } => bid_up_to(auction, (money - reserve).min(auctioned_price(game, auction) / 2)),
+            PendingDecision::PendingAuction { auction: None, .. } => BotAction::PassBid,
+            PendingDecision::EndTurn { .. } => Self::repay(game, player_id, reserve).unwrap_or(BotAction::EndTurn),
+            PendingDecision::InAction | PendingDecision::EndGame => BotAction::EndTurn,
+        })
+    }
+}
+
+impl GameEngine {
+    /// `player_id`를 봇이 조종하게 함 (이미 봇이면 전략을 바꿈)
+    pub fn set_bot(&mut self, player_id: u32, strategy: Box<dyn PlayerStrategy>) -> Result<(), GameError> {
+        if !self.state.players.iter().any(|player| player.id == player_id) {
+            return Err(GameError::UnknownPlayer(player_id));
+        }
+        self.bots.insert(player_id, strategy);
+        Ok(())
+    }
+
+    /// 지금 결정을 내려야 하는 봇 플레이어
+    ///
+    /// 경매에서는 최고 입찰자가 아닌 입찰자 중 첫 번째 봇이 부르거나 포기한다.
+    pub fn bot_to_act(&self) -> Option<u32> {
+        match self.pending_decision() {
+            PendingDecision::PendingAuction { auction: Some(auction), .. } => {
+                auction.bidders.iter().copied().find(|&pid| auction.highest_bidder != Some(pid) && self.bots.contains_key(&pid))
+            },
+            decision => decision.player_id().filter(|pid| self.bots.contains_key(pid)),
+        }
+    }
+
+    /// 결정을 내려야 하는 플레이어가 봇이라면 전략대로 명령을 하나 실행하고 그 결정을 반환
+    ///
+    /// 사람 플레이어의 결정을 기다리거나 게임이 끝났다면 `None`
+    pub fn step_bot(&mut self, scripts: &Scripts) -> Result<Option<BotAction>, GameError> {
+        let Some(player_id) = self.bot_to_act() else {
+            return Ok(None);
+        };
+        let mut decision = self.pending_decision();
+        if let PendingDecision::PendingCheckChanceCardResponse { card_id, options, .. } = &mut decision {
+            // 봇이 후보를 비교할 수 있도록 각 `payload`로 카드를 확인했을 때의 행동을 미리 구함
+            for option in options.iter_mut() {
+                option.preview = self.eval_chance_card(card_id, &scripts.chance, Some(&option.payload_json)).ok();
+            }
+        }
+        let mut strategy = self.bots.remove(&player_id).unwrap();
+        // 봇 스크립트가 끝나지 않아도 게임이 멈추지 않도록 결정하는 동안만 연산 수를 제한
+        self.engine.set_max_operations(self.const_or("BOT_SCRIPT_MAX_OPERATIONS", 100_000) as u64);
+        let action = strategy.decide(player_id, &decision, self);
+        self.engine.set_max_operations(0);
+        self.bots.insert(player_id, strategy);
+        let action = action?;
+        self.apply_bot_action(player_id, &action, scripts)?;
+        Ok(Some(action))
+    }
+
+    fn apply_bot_action(&mut self, player_id: u32, action: &BotAction, scripts: &Scripts) -> Result<(), GameError> {
+        match action {
+            BotAction::RollDice if matches!(self.now, GameSituation::PendingTryToJailbreakResponse | GameSituation::PendingJailbreakByDicesResponse) => self.roll_and_try_to_jailbreak().map(|_| ()),
+            BotAction::RollDice => self.roll_and_run_turn_script(&scripts.action, &scripts.cycle).map(|_| ()),
+            BotAction::AcceptPurchase => self.accept_purchase(),
+            BotAction::DeclinePurchase => self.decline_purchase(),
+            BotAction::UseTicket { tickets } => self.use_ticket(*tickets, &scripts.action, &scripts.cycle),
+            BotAction::LuckTest { double_lotto } => self.luck_test(*double_lotto),
+            BotAction::StopLuckTest => self.stop_luck_test(),
+            BotAction::TryToJailbreakByMoney => self.try_to_jailbreak_by_money(),
+            BotAction::GiveUpJailbreak => self.give_up_jailbreak(),
+            BotAction::GetRandomChanceCard => self.get_random_chance_card(),
+            BotAction::CheckChanceCard { payload_json } => self.check_chance_card(&scripts.chance, &scripts.cycle, payload_json.clone()),
+            BotAction::BorrowMoney { amount } => self.borrow_money(player_id, *amount),
+            BotAction::RepayLoan { loan_id, amount } => self.repay_loan(player_id, *loan_id, *amount),
+            BotAction::SellBuilding { tile_name } => self.sell_building(tile_name),
+            BotAction::MortgageProperty { tile_name } => self.mortgage_property(tile_name),
+            BotAction::RedeemMortgage { tile_name } => self.redeem_mortgage(tile_name),
+            BotAction::ResolveFinancialCrisis => self.resolve_financial_crisis(),
+            BotAction::DeclareBankruptcy => self.declare_bankruptcy(),
+            BotAction::AcceptTrade => self.accept_trade(),
+            BotAction::RejectTrade => self.reject_trade(),
+            BotAction::PlaceBid { amount } => self.place_bid(player_id, *amount),
+            BotAction::PassBid => self.pass_bid(player_id),
+            BotAction::EndTurn => self.end_turn(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 내장 전략의 봇이 `player_id`를 조종하게 함 (`seed`는 무작위 전략만 사용)
+    pub fn set_builtin_bot(&mut self, player_id: u32, strategy: BuiltinStrategy, seed: u64) -> Result<(), GameError> {
+        self.set_bot(player_id, strategy.build(seed))
+    }
+
+    /// `player_id`를 다시 사람이 조종하게 함 (봇이 아니었다면 `false`)
+    pub fn remove_bot(&mut self, player_id: u32) -> bool {
+        self.bots.remove(&player_id).is_some()
+    }
+
+    pub fn is_bot(&self, player_id: u32) -> bool {
+        self.bots.contains_key(&player_id)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// `step_bot` (봇이 내린 `BotAction`의 JSON, 봇의 차례가 아니면 `null`)
+    #[wasm_bindgen(js_name = step_bot)]
+    pub fn step_bot_json(&mut self, script_action: &str, script_cycle: &str, script_chance: &str) -> Result<String, GameError> {
+        let scripts = Scripts { action: script_action.into(), cycle: script_cycle.into(), chance: script_chance.into() };
+        Ok(serde_json::to_string(&self.step_bot(&scripts)?).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, BuiltinStrategy, ChanceCard, Creditor, GameEngine, GameSituation, Ownership, Purchase, TradeOffer, TradeSide};
+
+    /// 봇이 결정을 내려야 하는 상황마다 그 상황에 놓인 게임
+    fn situations() -> Vec<(&'static str, GameEngine)> {
+        let mut situations = Vec::new();
+        situations.push(("roll", testing::game(&[])));
+
+        let mut game = testing::game(&[]);
+        game.roll_dice().unwrap();
+        situations.push(("move", game));
+
+        let mut game = testing::game(&[]);
+        game.state.players[0].position = 2;
+        game.state.pending_purchase = Some(Purchase { tile_name: "Busan".into(), land_price: Some(200_000) });
+        game.now = GameSituation::PendingBuyResponse;
+        situations.push(("buy", game));
+
+        let mut game = testing::game(&[]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 1, mortgaged: false });
+        game.state.players[0].money = -10_000;
+        game.prompt_financial_crisis(Creditor::Market);
+        situations.push(("crisis", game));
+
+        let mut game = testing::game(&[]);
+        game.now = GameSituation::PendingLuckTestResponse;
+        situations.push(("luck test", game));
+
+        let mut game = testing::game(&[]);
+        game.state.players[0].position = 3;
+        game.state.players[0].remaining_jail_turns = 2;
+        game.state.players[0].tickets_count.release_from_jail = 1;
+        game.now = GameSituation::PendingUseTicketResponse;
+        situations.push(("use ticket", game));
+
+        let mut game = testing::game(&[]);
+        game.state.players[0].position = 3;
+        game.state.players[0].remaining_jail_turns = 2;
+        game.now = GameSituation::PendingTryToJailbreakResponse;
+        situations.push(("jail", game));
+
+        let mut game = testing::game(&[]);
+        game.state.players[0].position = 3;
+        game.state.players[0].remaining_jail_turns = 2;
+        game.now = GameSituation::PendingTryToJailbreakResponse;
+        game.roll_dice().unwrap();
+        situations.push(("jailbreak by dices", game));
+
+        let mut game = testing::game(&[]);
+        let card = ChanceCard { title: "Lotto".into(), descriptoin: String::new(), instruction: String::new(), payload_options: Vec::new() };
+        game.state.chance_cards_inventory.insert("lotto".into(), card);
+        game.now = GameSituation::PendingGetRandomChanceCardResponse;
+        situations.push(("draw chance card", game));
+
+        let mut game = testing::game(&[]);
+        game.pending_chance_card_id = Some("lotto".into());
+        game.now = GameSituation::PendingCheckChanceCardResponse;
+        situations.push(("check chance card", game));
+
+        let mut game = testing::game(&[]);
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 2, buildings: 0, mortgaged: false });
+        game.now = GameSituation::EndTurn;
+        let offer = TradeOffer {
+            give: TradeSide { money: 300_000, ..TradeSide::default() },
+            take: TradeSide { properties: vec!["Busan".into()], ..TradeSide::default() },
+        };
+        game.propose_trade(1, 2, offer).unwrap();
+        situations.push(("trade", game));
+
+        let mut game = testing::game(&[("AUCTION_ENABLED", 1)]);
+        game.start_auction("Busan");
+        situations.push(("auction", game));
+
+        let mut game = testing::game(&[]);
+        game.now = GameSituation::EndTurn;
+        situations.push(("end turn", game));
+        situations
+    }
+
+    #[test]
+    fn builtin_strategies_make_a_legal_move_in_every_situation() {
+        let scripts = testing::scripts();
+        let strategies = [(BuiltinStrategy::Greedy, 0), (BuiltinStrategy::Conservative, 0)].into_iter()
+            .chain((0..8).map(|seed| (BuiltinStrategy::Random, seed)));
+        for (strategy, seed) in strategies {
+            for (name, mut game) in situations() {
+                game.set_builtin_bot(1, strategy, seed).unwrap();
+                game.set_builtin_bot(2, strategy, seed).unwrap();
+                let steps = game.replay_document().steps.len();
+                let action = game.step_bot(&scripts);
+                assert!(matches!(action, Ok(Some(_))), "{} (seed {}) while {}: {:?}", strategy.name(), seed, name, action);
+                assert!(game.replay_document().steps.len() > steps, "{} (seed {}) did nothing while {}", strategy.name(), seed, name);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rhai::{Dynamic, Map, Scope, AST};
+use serde::Serialize;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{BotAction, GameEngine, GameError, LiquidationOption, Ownership, PendingDecision, Player, PlayerStrategy, Tile};
+
+/// 봇 스크립트에 `state`로 넘기는 `GameState`의 사본
+///
+/// 사본이므로 스크립트가 고쳐도 게임에는 영향이 없다.
+#[derive(Serialize)]
+struct StateView<'a> {
+    board: &'a [Tile],
+    players: &'a [Player],
+    properties: &'a HashMap<String, Ownership>,
+    consts: &'a HashMap<String, u32>,
+    current_player_id: u32,
+    government_income: i64,
+    turn_count: u32,
+    /// 플레이어 id(문자열)별 순자산
+    net_worths: BTreeMap<String, i64>,
+    /// 현재 플레이어가 재정 위기 중에 선택할 수 있는 처분 방법
+    liquidation_options: Vec<LiquidationOption>,
+}
+
+impl<'a> StateView<'a> {
+    fn of(game: &'a GameEngine) -> Self {
+        let state = &game.state;
+        StateView {
+            board: &state.board,
+            players: &state.players,
+            properties: &state.properties,
+            consts: &state.consts,
+            current_player_id: state.current_player().id,
+            government_income: state.government_income,
+            turn_count: state.turn_count,
+            net_worths: state.players.iter().map(|player| (player.id.to_string(), game.net_worth(player))).collect(),
+            liquidation_options: game.liquidation_options(),
+        }
+    }
+}
+
+impl BotAction {
+    fn from_map(map: &Map) -> Result<Self, GameError> {
+        let action = match map.get("action") {
+            Some(value) => value.clone().into_string().map_err(|t| GameError::InvalidScriptResult(format!("Bot script returned 'action' of type '{}', expected a string.", t)))?,
+            None => return Err(GameError::InvalidScriptResult("Bot script returned a map without 'action'.".into())),
+        };
+        rhai::serde::from_dynamic(&Dynamic::from_map(map.clone())).map_err(|e| GameError::InvalidScriptResult(format!("Invalid '{}' decision from Bot script: {}", action, e)))
+    }
+}
+
+/// Rhai 스크립트로 작성한 봇 전략
+///
+/// 스크립트는 룰 스크립트와 같은 엔진에서 실행되며, 다음 상수를 받아 `BotAction` 모양의 맵을 반환한다.
+/// - `player_id`: 봇이 조종하는 플레이어
+/// - `decision`: 내려야 하는 결정 (`PendingDecision`, `situation` 필드로 구분)
+/// - `state`: 보드, 플레이어, 부동산, 상수, 순자산 등의 사본
+///
+/// 예를 들어 `#{action: "accept_purchase"}`나 `#{action: "place_bid", amount: 100000}`를 반환한다.
+/// 한 번의 결정에 쓸 수 있는 연산 수는 `BOT_SCRIPT_MAX_OPERATIONS`(기본 100000)로 제한된다.
+pub struct ScriptStrategy {
+    ast: AST,
+}
+
+impl ScriptStrategy {
+    /// `game`의 스크립트 엔진으로 미리 컴파일 (문법 오류는 여기서 드러남)
+    pub fn new(game: &GameEngine, script: &str) -> Result<Self, GameError> {
+        let ast = game.engine.compile(script).map_err(|e| GameError::ScriptError(e.to_string()))?;
+        Ok(Self { ast })
+    }
+}
+
+impl PlayerStrategy for ScriptStrategy {
+    fn decide(&mut self, player_id: u32, decision: &PendingDecision, game: &GameEngine) -> Result<BotAction, GameError> {
+        let mut scope = Scope::new();
+        scope.push_constant("player_id", player_id as i64);
+        scope.push_constant("decision", rhai::serde::to_dynamic(decision)?);
+        scope.push_constant("state", rhai::serde::to_dynamic(StateView::of(game))?);
+        let result: Map = game.engine.eval_ast_with_scope(&mut scope, &self.ast)?;
+        BotAction::from_map(&result)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// Rhai 전략 스크립트의 봇이 `player_id`를 조종하게 함 (`ScriptStrategy` 참고)
+    pub fn set_script_bot(&mut self, player_id: u32, script: &str) -> Result<(), GameError> {
+        let strategy = ScriptStrategy::new(self, script)?;
+        self.set_bot(player_id, Box::new(strategy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, BotAction, GameError, GameSituation};
+
+    #[test]
+    fn script_bot_decides_from_the_pending_decision() {
+        let mut game = testing::game(&[]);
+        game.set_script_bot(1, r#"if decision.situation == "EndTurn" { #{ action: "end_turn" } } else { #{ action: "roll_dice" } }"#).unwrap();
+        game.now = GameSituation::EndTurn;
+        assert_eq!(game.step_bot(&testing::scripts()), Ok(Some(BotAction::EndTurn)));
+    }
+
+    #[test]
+    fn endless_script_bot_stops_at_the_operation_limit() {
+        let mut game = testing::game(&[("BOT_SCRIPT_MAX_OPERATIONS", 1_000)]);
+        game.set_script_bot(1, "loop { }").unwrap();
+        let scripts = testing::scripts();
+        assert!(matches!(game.step_bot(&scripts), Err(GameError::ScriptError(_))));
+        assert_eq!(game.now, GameSituation::PendingRollResponse);
+
+        // 제한은 봇이 결정하는 동안에만 걸림
+        let long = "let sum = 0; for i in 0..2000 { sum += i; } sum";
+        assert_eq!(game.engine.eval::<i64>(long).unwrap(), 1_999_000);
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameEngine, Tile};
+#[cfg(feature = "wasm")]
+use crate::GameError;
+
+/// 한 타일에 지을 수 있는 건물의 한 단계 (주택, 호텔, 랜드마크 등)
+///
+/// 건물은 `Tile.buildings`에 적힌 단계 순서대로 지어진다.
+/// 예를 들어 `[주택 ×2, 호텔 ×1]`이면 첫째와 둘째 건물은 주택, 셋째 건물은 호텔이다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuildingTier {
+    pub name: String,
+    pub cost: i64,
+    #[serde(default = "BuildingTier::default_max_count")]
+    pub max_count: u32,
+    /// 지진, 파괴 카드로 부서지지 않음
+    #[serde(default)]
+    pub indestructible: bool,
+}
+
+impl BuildingTier {
+    fn default_max_count() -> u32 {
+        1
+    }
+}
+
+impl GameEngine {
+    pub(crate) fn tile_by_name(&self, tile_name: &str) -> Option<&Tile> {
+        self.state.board.iter().find(|tile| tile.name == tile_name)
+    }
+
+    /// 타일의 건물 단계
+    ///
+    /// `Tile.buildings`가 비어 있으면 `building_cost`짜리 건물을
+    /// `MAX_BUILDINGS`(기본 1, 0이면 1)채까지 지을 수 있는 단계 하나로 본다.
+    pub fn building_tiers(&self, tile: &Tile) -> Vec<BuildingTier> {
+        if tile.buildings.is_empty() {
+            vec![BuildingTier {
+                name: "Building".into(),
+                cost: self.building_cost,
+                max_count: self.const_or("MAX_BUILDINGS", 1).max(1),
+                indestructible: false,
+            }]
+        } else {
+            tile.buildings.clone()
+        }
+    }
+
+    pub fn max_buildings(&self, tile: &Tile) -> u32 {
+        self.building_tiers(tile).iter().map(|tier| tier.max_count).sum()
+    }
+
+    /// `index`번째(0부터) 건물의 단계
+    pub fn building_tier_at(&self, tile: &Tile, index: u32) -> Option<BuildingTier> {
+        let mut remaining = index;
+        self.building_tiers(tile).into_iter().find(|tier| {
+            if remaining < tier.max_count {
+                true
+            } else {
+                remaining -= tier.max_count;
+                false
+            }
+        })
+    }
+
+    /// 다음에 지을 건물의 단계 (더 지을 수 없으면 `None`)
+    pub(crate) fn next_building_tier(&self, tile_name: &str) -> Option<BuildingTier> {
+        let tile = self.tile_by_name(tile_name)?;
+        let buildings = self.state.properties.get(tile_name).map(|ownership| ownership.buildings).unwrap_or(0);
+        self.building_tier_at(tile, buildings)
+    }
+
+    /// 지어진 건물들의 건설 비용 합
+    pub(crate) fn buildings_value(&self, tile_name: &str, buildings: u32) -> i64 {
+        self.tile_by_name(tile_name).map(|tile| {
+            (0..buildings).filter_map(|index| self.building_tier_at(tile, index)).map(|tier| tier.cost).sum()
+        }).unwrap_or(0)
+    }
+
+    /// 가장 나중에 지은 건물이 부서질 수 있는지
+    pub(crate) fn is_top_building_destructible(&self, tile_name: &str, buildings: u32) -> bool {
+        buildings > 0 && self.tile_by_name(tile_name)
+            .and_then(|tile| self.building_tier_at(tile, buildings - 1))
+            .is_none_or(|tier| !tier.indestructible)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 타일의 건물 단계 (`BuildingTier` 배열의 JSON)
+    pub fn get_building_tiers(&self, tile_name: &str) -> Result<String, GameError> {
+        let tile = self.tile_by_name(tile_name).ok_or_else(|| GameError::TileNotFound(tile_name.into()))?;
+        Ok(serde_json::to_string(&self.building_tiers(tile)).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, BuildingTier, GameEngine, GameError, GameEvent, GameSituation, Ownership, Purchase};
+
+    fn tier(name: &str, cost: i64, max_count: u32, indestructible: bool) -> BuildingTier {
+        BuildingTier { name: name.into(), cost, max_count, indestructible }
+    }
+
+    /// 서울에 주택 두 채와 부서지지 않는 호텔 한 채를 지을 수 있는 게임
+    fn tiered_game(consts: &[(&str, u32)]) -> GameEngine {
+        let mut game = testing::game(consts);
+        game.state.board[1].buildings = vec![tier("House", 10_000, 2, false), tier("Hotel", 50_000, 1, true)];
+        game
+    }
+
+    #[test]
+    fn tiers_are_built_in_order() {
+        let game = tiered_game(&[("BUILDING_SALE_PERCENT", 40)]);
+        let seoul = &game.state.board[1];
+        assert_eq!(game.max_buildings(seoul), 3);
+        assert_eq!(game.building_tier_at(seoul, 1).map(|tier| tier.name), Some("House".into()));
+        assert_eq!(game.building_tier_at(seoul, 2).map(|tier| tier.name), Some("Hotel".into()));
+        assert_eq!(game.building_tier_at(seoul, 3), None);
+        assert_eq!(game.buildings_value("Seoul", 3), 70_000);
+        assert_eq!(game.building_sale_value("Seoul", 2), 4_000);
+        assert_eq!(game.building_sale_value("Seoul", 3), 20_000);
+    }
+
+    #[test]
+    fn tiles_without_tiers_use_the_building_cost_up_to_the_limit() {
+        let game = testing::game(&[("MAX_BUILDINGS", 2)]);
+        let busan = &game.state.board[2];
+        assert_eq!(game.building_tiers(busan), [tier("Building", 100_000, 2, false)]);
+        assert_eq!(game.buildings_value("Busan", 2), 200_000);
+        assert_eq!(game.building_sale_value("Busan", 1), 50_000);
+    }
+
+    #[test]
+    fn nothing_more_is_built_past_the_limit() {
+        let mut game = tiered_game(&[]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 3, mortgaged: false });
+        assert_eq!(game.next_building_tier("Seoul"), None);
+
+        game.offer_purchase("Seoul", None);
+        assert!(game.state.events.contains(&GameEvent::BuildingLimitReached { tile_name: "Seoul".into() }));
+        assert_ne!(game.now, GameSituation::PendingBuyResponse);
+
+        game.state.pending_purchase = Some(Purchase { tile_name: "Seoul".into(), land_price: None });
+        game.now = GameSituation::PendingBuyResponse;
+        assert!(matches!(game.accept_purchase(), Err(GameError::BuildingLimitReached(name)) if name == "Seoul"));
+        assert_eq!(game.state.properties["Seoul"].buildings, 3);
+        assert_eq!(game.state.players[0].money, 1_000_000);
+    }
+
+    #[test]
+    fn earthquake_spares_indestructible_buildings() {
+        let mut game = tiered_game(&[]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 3, mortgaged: false });
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 1, buildings: 1, mortgaged: false });
+        game.pending_chance_card_id = Some("earthquake".into());
+        game.now = GameSituation::PendingCheckChanceCardResponse;
+
+        let scripts = testing::scripts();
+        game.check_chance_card(r#"#{ type: "Earthquake" }"#, &scripts.cycle, None).unwrap();
+        assert_eq!(game.state.properties["Seoul"].buildings, 3);
+        assert_eq!(game.state.properties["Busan"].buildings, 0);
+        assert_eq!(game.state.properties["Busan"].owner_id, 1);
+    }
+}
@@ -0,0 +1,283 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, GameEngine, GameError, GameEvent, GameSituation, Payment, ReplayStep};
+
+/// 재정 위기를 일으킨 빚을 받을 쪽
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Creditor {
+    Government,
+    Market,
+    Player(u32),
+}
+
+impl Payment {
+    /// 다른 플레이어에게 지불했다면 그 플레이어, 아니면 정부나 시장
+    pub(crate) fn creditor(&self) -> Creditor {
+        match (self.player_amount, self.to_player_id, self.gov_amount) {
+            (Some(_), Some(pid), _) => Creditor::Player(pid),
+            (_, _, Some(_)) => Creditor::Government,
+            _ => Creditor::Market,
+        }
+    }
+}
+
+/// 재정 위기 중에 현금을 마련할 수 있는 방법
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum LiquidationOption {
+    /// 건물 한 채를 시장에 되팖 (마지막 건물을 팔아도 땅은 남음)
+    SellBuilding { tile_name: String, value: i64 },
+    Mortgage { tile_name: String, value: i64 },
+    EmergencyLoan { amount: i64 },
+}
+
+impl GameEngine {
+    /// 건물이 `buildings`채 있을 때 가장 나중에 지은 건물을 되팔아 받는 금액
+    /// (그 건물 비용의 `BUILDING_SALE_PERCENT`, 기본 50%)
+    pub(crate) fn building_sale_value(&self, tile_name: &str, buildings: u32) -> i64 {
+        let cost = self.tile_by_name(tile_name)
+            .and_then(|tile| self.building_tier_at(tile, buildings.saturating_sub(1)))
+            .map(|tier| tier.cost)
+            .unwrap_or(0);
+        cost * self.const_or("BUILDING_SALE_PERCENT", 50) as i64 / 100
+    }
+
+    /// 부동산을 저당 잡힐 때 받는 금액 (`MORTGAGE_PERCENT`, 기본 50%)
+    pub(crate) fn mortgage_value(&self, tile_name: &str) -> Result<i64, GameError> {
+        let tile = self.state.board.iter().find(|tile| tile.name == tile_name).ok_or_else(|| GameError::TileNotFound(tile_name.into()))?;
+        Ok(tile.price * self.const_or("MORTGAGE_PERCENT", 50) as i64 / 100)
+    }
+
+    pub(crate) fn prompt_financial_crisis(&mut self, creditor: Creditor) {
+        self.state.crisis_creditor = Some(creditor);
+        self.now = GameSituation::PendingFinancialCrisisResponse;
+    }
+
+    /// 재정 위기 중 현금이 음수가 아니게 되었다면 위기를 끝냄
+    pub(crate) fn settle_financial_crisis(&mut self) {
+        if self.now == GameSituation::PendingFinancialCrisisResponse && self.state.players[self.state.current_turn_idx].money >= 0 {
+            self.state.crisis_creditor = None;
+            let player_id = self.state.players[self.state.current_turn_idx].id;
+            self.emit(GameEvent::FinancialCrisisResolved { player_id });
+            self.now = GameSituation::EndTurn;
+        }
+    }
+
+    pub fn liquidation_options(&self) -> Vec<LiquidationOption> {
+        let player = &self.state.players[self.state.current_turn_idx];
+        let mut owned = self.state.properties.iter().filter(|(_, ownership)| ownership.owner_id == player.id).collect::<Vec<_>>();
+        owned.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut options = owned.iter().flat_map(|&(name, ownership)| {
+            let sell = (ownership.buildings > 0).then(|| LiquidationOption::SellBuilding { tile_name: name.clone(), value: self.building_sale_value(name, ownership.buildings) });
+            let mortgage = if ownership.mortgaged { None } else {
+                self.mortgage_value(name).ok().map(|value| LiquidationOption::Mortgage { tile_name: name.clone(), value })
+            };
+            sell.into_iter().chain(mortgage)
+        }).collect::<Vec<_>>();
+
+        // 대출 한도가 있다면 한도까지만 빌릴 수 있음
+        let amount = (-player.money).max(0).min(self.loan_limit(player).unwrap_or(i64::MAX));
+        if amount > 0 {
+            options.push(LiquidationOption::EmergencyLoan { amount });
+        }
+        options
+    }
+
+    /// 대출을 제외하고 현재 플레이어의 자산을 모두 처분해 마련할 수 있는 최대 금액
+    pub fn liquidation_value(&self) -> i64 {
+        let player_id = self.state.players[self.state.current_turn_idx].id;
+        self.state.properties.iter().filter(|(_, ownership)| ownership.owner_id == player_id).map(|(name, ownership)| {
+            let sell_all = (1..=ownership.buildings).map(|buildings| self.building_sale_value(name, buildings)).sum::<i64>();
+            let mortgage = if ownership.mortgaged { 0 } else { self.mortgage_value(name).unwrap_or(0) };
+            sell_all + mortgage
+        }).sum()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    pub fn sell_building(&mut self, tile_name: &str) -> Result<(), GameError> {
+        self.expect(Command::SellBuilding)?;
+        self.record(ReplayStep::SellBuilding { tile_name: tile_name.into() }, |game| {
+            let player_index = game.state.current_turn_idx;
+            let player_id = game.state.players[player_index].id;
+            let buildings = game.state.properties.get(tile_name).filter(|ownership| ownership.owner_id == player_id).ok_or_else(|| GameError::UnownedProperty(tile_name.into()))?.buildings;
+            if buildings == 0 {
+                return Err(GameError::NoBuildingToSell(tile_name.into()));
+            }
+            let value = game.building_sale_value(tile_name, buildings);
+            let ownership = game.state.properties.get_mut(tile_name).unwrap();
+            ownership.buildings -= 1;
+            game.state.players[player_index].money += value;
+            game.emit(GameEvent::BuildingSold { player_id, tile_name: tile_name.into(), amount: value });
+            game.settle_financial_crisis();
+            Ok(())
+        })
+    }
+
+    pub fn mortgage_property(&mut self, tile_name: &str) -> Result<(), GameError> {
+        self.expect(Command::MortgageProperty)?;
+        self.record(ReplayStep::MortgageProperty { tile_name: tile_name.into() }, |game| {
+            let player_index = game.state.current_turn_idx;
+            let player_id = game.state.players[player_index].id;
+            let value = game.mortgage_value(tile_name)?;
+            let ownership = game.state.properties.get_mut(tile_name).filter(|ownership| ownership.owner_id == player_id && !ownership.mortgaged).ok_or_else(|| GameError::UnownedProperty(tile_name.into()))?;
+            ownership.mortgaged = true;
+            game.state.players[player_index].money += value;
+            game.emit(GameEvent::Mortgaged { player_id, tile_name: tile_name.into(), amount: value });
+            game.settle_financial_crisis();
+            Ok(())
+        })
+    }
+
+    /// 저당 금액에 10%를 더해 갚고 저당을 해제
+    pub fn redeem_mortgage(&mut self, tile_name: &str) -> Result<(), GameError> {
+        self.expect(Command::RedeemMortgage)?;
+        self.record(ReplayStep::RedeemMortgage { tile_name: tile_name.into() }, |game| {
+            let player_index = game.state.current_turn_idx;
+            let player_id = game.state.players[player_index].id;
+            let value = game.mortgage_value(tile_name)?;
+            let cost = value + value / 10;
+            let money = game.state.players[player_index].money;
+            if money < cost {
+                return Err(GameError::InsufficientFunds { required: cost, available: money });
+            }
+            let ownership = game.state.properties.get_mut(tile_name).filter(|ownership| ownership.owner_id == player_id && ownership.mortgaged).ok_or_else(|| GameError::UnownedProperty(tile_name.into()))?;
+            ownership.mortgaged = false;
+            game.state.players[player_index].money -= cost;
+            game.emit(GameEvent::MortgageRedeemed { player_id, tile_name: tile_name.into(), amount: cost });
+            Ok(())
+        })
+    }
+
+    /// 현금이 음수가 아니라면 재정 위기를 끝냄
+    pub fn resolve_financial_crisis(&mut self) -> Result<(), GameError> {
+        self.expect(Command::ResolveFinancialCrisis)?;
+        self.record(ReplayStep::ResolveFinancialCrisis, |game| {
+            let money = game.state.players[game.state.current_turn_idx].money;
+            if money < 0 {
+                return Err(GameError::InsufficientFunds { required: -money, available: 0 });
+            }
+            game.settle_financial_crisis();
+            Ok(())
+        })
+    }
+
+    /// 자산을 모두 처분해도 부족액을 메울 수 없을 때 파산을 선언
+    ///
+    /// 채권자가 플레이어라면 부동산과 티켓을 그 플레이어에게 넘기고, 아니라면 시장에 내놓는다.
+    pub fn declare_bankruptcy(&mut self) -> Result<(), GameError> {
+        self.expect(Command::DeclareBankruptcy)?;
+        self.record(ReplayStep::DeclareBankruptcy, |game| game.go_bankrupt())
+    }
+
+    /// 파산 처리: 부동산과 티켓을 채권자나 시장에 넘기고 플레이어를 탈락시킴
+    fn go_bankrupt(&mut self) -> Result<(), GameError> {
+        let player_index = self.state.current_turn_idx;
+        let deficit = (-self.state.players[player_index].money).max(0);
+        let liquidation_value = self.liquidation_value();
+        if liquidation_value >= deficit {
+            return Err(GameError::NotBankrupt { deficit, liquidation_value });
+        }
+
+        let player_id = self.state.players[player_index].id;
+        let creditor = self.state.crisis_creditor.take().unwrap_or(Creditor::Market);
+        let heir = match creditor {
+            Creditor::Player(pid) if pid != player_id && self.state.players.iter().any(|player| player.id == pid && !player.bankrupt) => Some(pid),
+            _ => None,
+        };
+
+        let tickets = std::mem::take(&mut self.state.players[player_index].tickets_count);
+        match heir {
+            Some(pid) => {
+                self.state.properties.values_mut().filter(|ownership| ownership.owner_id == player_id).for_each(|ownership| {
+                    ownership.owner_id = pid;
+                });
+                if let Some(heir_mut) = self.state.players.iter_mut().find(|player| player.id == pid) {
+                    heir_mut.tickets_count += tickets;
+                }
+                self.emit(GameEvent::Bankrupt { player_id, heir_id: Some(pid) });
+            },
+            None => {
+                self.state.properties.retain(|_, ownership| ownership.owner_id != player_id);
+                self.emit(GameEvent::Bankrupt { player_id, heir_id: None });
+            }
+        }
+
+        let player_mut = &mut self.state.players[player_index];
+        player_mut.bankrupt = true;
+        player_mut.money = 0;
+        player_mut.remaining_loans.clear();
+        self.state.eliminations.push(player_id);
+        self.now = GameSituation::EndTurn;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 재정 위기 중에 선택할 수 있는 처분 방법 (`LiquidationOption` 배열의 JSON)
+    pub fn get_liquidation_options(&self) -> String {
+        serde_json::to_string(&self.liquidation_options()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, Creditor, GameEngine, GameError, GameEvent, GameSituation, Ownership};
+
+    /// 플레이어 1이 서울을 저당 잡힌 채 `money`만 가지고 `creditor`에게 진 빚으로 재정 위기에 빠진 게임
+    fn crisis(creditor: Creditor, money: i64, mortgaged: bool) -> GameEngine {
+        let mut game = testing::game(&[]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 0, mortgaged });
+        game.state.players[0].tickets_count.bonus = 1;
+        game.state.players[0].money = money;
+        game.prompt_financial_crisis(creditor);
+        game
+    }
+
+    #[test]
+    fn bankrupt_player_leaves_properties_and_tickets_to_the_creditor() {
+        let mut game = crisis(Creditor::Player(2), -500_000, true);
+        game.declare_bankruptcy().unwrap();
+
+        assert_eq!(game.state.properties["Seoul"].owner_id, 2);
+        assert_eq!(game.state.players[1].tickets_count.bonus, 1);
+        assert_eq!(game.state.players[0].tickets_count.bonus, 0);
+        assert!(game.state.players[0].bankrupt);
+        assert_eq!(game.state.players[0].money, 0);
+        assert!(game.state.events.contains(&GameEvent::Bankrupt { player_id: 1, heir_id: Some(2) }));
+    }
+
+    #[test]
+    fn bankrupt_player_returns_properties_to_the_market() {
+        let mut game = crisis(Creditor::Market, -500_000, true);
+        game.declare_bankruptcy().unwrap();
+
+        assert!(!game.state.properties.contains_key("Seoul"));
+        assert_eq!(game.state.players[1].tickets_count.bonus, 0);
+        assert!(game.state.events.contains(&GameEvent::Bankrupt { player_id: 1, heir_id: None }));
+    }
+
+    #[test]
+    fn bankruptcy_is_refused_while_liquidation_covers_the_deficit() {
+        let mut game = crisis(Creditor::Player(2), -10_000, false);
+        assert!(matches!(game.declare_bankruptcy(), Err(GameError::NotBankrupt { deficit: 10_000, liquidation_value: 50_000 })));
+        assert!(!game.state.players[0].bankrupt);
+        assert_eq!(game.state.properties["Seoul"].owner_id, 1);
+        assert_eq!(game.now, GameSituation::PendingFinancialCrisisResponse);
+    }
+
+    #[test]
+    fn selling_needs_a_building_on_the_property() {
+        let mut game = crisis(Creditor::Player(2), -10_000, false);
+        assert!(matches!(game.sell_building("Seoul"), Err(GameError::NoBuildingToSell(name)) if name == "Seoul"));
+        assert!(matches!(game.sell_building("Busan"), Err(GameError::UnownedProperty(name)) if name == "Busan"));
+        assert_eq!(game.state.players[0].money, -10_000);
+        assert_eq!(game.now, GameSituation::PendingFinancialCrisisResponse);
+    }
+}
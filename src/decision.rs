@@ -0,0 +1,116 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{Auction, BuildingTier, ChanceAction, ChanceCard, DicePair, GameEngine, GameSituation, TicketCount, Tile, Trade};
+
+/// 엔진이 기다리고 있는 결정과 그 결정에 필요한 맥락
+///
+/// `situation` 필드로 구분되며, 이름은 `GameSituation`과 같다.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "situation")]
+pub enum PendingDecision {
+    InAction,
+    PendingRollResponse { player_id: u32 },
+    /// `land_price`가 있으면 땅을, 없으면 `building` 단계의 건물을 살지 묻는 중
+    PendingBuyResponse { player_id: u32, position: u32, tile: Tile, land_price: Option<i64>, building_cost: i64, building: Option<BuildingTier> },
+    /// `deficit`은 플레이어가 갚아야 할 부족액 (양수)
+    PendingFinancialCrisisResponse { player_id: u32, deficit: i64 },
+    /// `pot`이 음수면 아직 행운 시험을 시작하지 않은 상태
+    PendingLuckTestResponse { player_id: u32, pot: i64 },
+    PendingUseTicketResponse { player_id: u32, usable_tickets: TicketCount },
+    PendingTryToJailbreakResponse { player_id: u32, remaining_jail_turns: u32 },
+    PendingGetRandomChanceCardResponse { player_id: u32 },
+    /// `options`는 카드에 넘길 수 있는 `payload` 후보 (비어 있으면 `payload` 없이 확인)
+    PendingCheckChanceCardResponse { player_id: u32, card_id: String, card: Option<ChanceCard>, options: Vec<ChanceOption> },
+    /// `player_id`는 거래에 응답해야 하는 플레이어
+    PendingTradeResponse { player_id: u32, trade: Option<Trade> },
+    /// `player_id`는 경매 대상 타일에 도착한 플레이어
+    PendingAuction { player_id: u32, auction: Option<Auction> },
+    /// `dices`는 엔진이 굴린 주사위
+    PendingMoveResponse { player_id: u32, dices: Option<DicePair> },
+    PendingJailbreakByDicesResponse { player_id: u32, dices: Option<DicePair> },
+    EndTurn { player_id: u32 },
+    EndGame,
+}
+
+/// 찬스 카드에 넘길 수 있는 `payload` 하나
+///
+/// `preview`는 이 `payload`로 카드를 확인하면 일어날 행동이며, 봇이 결정할 때만 채워진다.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChanceOption {
+    pub payload_json: String,
+    pub preview: Option<ChanceAction>,
+}
+
+impl GameEngine {
+    pub fn pending_decision(&self) -> PendingDecision {
+        let player = &self.state.players[self.state.current_turn_idx];
+        let player_id = player.id;
+        match self.now {
+            GameSituation::InAction => PendingDecision::InAction,
+            GameSituation::PendingRollResponse => PendingDecision::PendingRollResponse { player_id },
+            GameSituation::PendingBuyResponse => {
+                let tile = self.state.board[player.position as usize].clone();
+                let land_price = self.current_purchase().land_price;
+                let building = self.next_building_tier(&tile.name);
+                let building_cost = building.as_ref().map(|tier| tier.cost).unwrap_or(0);
+                PendingDecision::PendingBuyResponse { player_id, position: player.position, tile, land_price, building_cost, building }
+            },
+            GameSituation::PendingFinancialCrisisResponse => PendingDecision::PendingFinancialCrisisResponse { player_id, deficit: (-player.money).max(0) },
+            GameSituation::PendingLuckTestResponse => PendingDecision::PendingLuckTestResponse { player_id, pot: self.state.luck_test_cache },
+            GameSituation::PendingUseTicketResponse => {
+                // 제안된 티켓 중 플레이어가 실제로 가지고 있는 것만
+                let (offered, owned) = (self.state.pending_ticket, player.tickets_count);
+                let usable_tickets = TicketCount {
+                    free_hospital: offered.free_hospital.min(owned.free_hospital),
+                    free_property: offered.free_property.min(owned.free_property),
+                    double_lotto: offered.double_lotto.min(owned.double_lotto),
+                    no_tax: offered.no_tax.min(owned.no_tax),
+                    release_from_jail: offered.release_from_jail.min(owned.release_from_jail),
+                    bonus: offered.bonus.min(owned.bonus),
+                };
+                PendingDecision::PendingUseTicketResponse { player_id, usable_tickets }
+            },
+            GameSituation::PendingTryToJailbreakResponse => PendingDecision::PendingTryToJailbreakResponse { player_id, remaining_jail_turns: player.remaining_jail_turns },
+            GameSituation::PendingGetRandomChanceCardResponse => PendingDecision::PendingGetRandomChanceCardResponse { player_id },
+            GameSituation::PendingCheckChanceCardResponse => {
+                let card_id = self.pending_chance_card_id.clone().unwrap_or_default();
+                let card = self.state.chance_cards_inventory.get(&card_id).cloned();
+                let options = card.iter().flat_map(|card| &card.payload_options).map(|payload| {
+                    ChanceOption { payload_json: payload.to_string(), preview: None }
+                }).collect();
+                PendingDecision::PendingCheckChanceCardResponse { player_id, card_id, card, options }
+            },
+            GameSituation::PendingTradeResponse => {
+                let trade = self.state.pending_trade.clone();
+                PendingDecision::PendingTradeResponse { player_id: trade.as_ref().map(|trade| trade.to).unwrap_or(player_id), trade }
+            },
+            GameSituation::PendingAuction => PendingDecision::PendingAuction { player_id, auction: self.state.auction.clone() },
+            GameSituation::PendingMoveResponse => PendingDecision::PendingMoveResponse { player_id, dices: self.state.rolled_dice },
+            GameSituation::PendingJailbreakByDicesResponse => PendingDecision::PendingJailbreakByDicesResponse { player_id, dices: self.state.rolled_dice },
+            GameSituation::EndTurn => PendingDecision::EndTurn { player_id },
+            GameSituation::EndGame => PendingDecision::EndGame,
+        }
+    }
+}
+
+impl PendingDecision {
+    /// 결정을 내려야 하는 플레이어 (경매는 도착한 플레이어)
+    pub fn player_id(&self) -> Option<u32> {
+        match self {
+            Self::InAction | Self::EndGame => None,
+            Self::PendingRollResponse { player_id }
+            | Self::PendingBuyResponse { player_id, .. }
+            | Self::PendingFinancialCrisisResponse { player_id, .. }
+            | Self::PendingLuckTestResponse { player_id, .. }
+            | Self::PendingUseTicketResponse { player_id, .. }
+            | Self::PendingTryToJailbreakResponse { player_id, .. }
+            | Self::PendingGetRandomChanceCardResponse { player_id }
+            | Self::PendingCheckChanceCardResponse { player_id, .. }
+            | Self::PendingTradeResponse { player_id, .. }
+            | Self::PendingAuction { player_id, .. }
+            | Self::PendingMoveResponse { player_id, .. }
+            | Self::PendingJailbreakByDicesResponse { player_id, .. }
+            | Self::EndTurn { player_id } => Some(*player_id),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{DicePair, GameError, GameRng};
+
+/// 주사위 한 개의 눈의 최댓값
+pub const DICE_FACES: u16 = 6;
+
+/// 엔진이 굴리는 주사위의 출처
+///
+/// 어떤 출처든 `roll`의 결과는 엔진이 `DicePair::validate`로 다시 검증한다.
+pub trait DiceSource {
+    fn roll(&mut self, rng: &mut GameRng) -> Result<DicePair, GameError>;
+
+    /// 다음 주사위를 게임 상태의 난수 생성기로 굴리는지 (리플레이에서 난수를 똑같이 소비하기 위함)
+    fn is_seeded(&self) -> bool {
+        false
+    }
+
+    /// 엔진이 굴리지 않고 호스트가 넘긴 주사위로 움직이거나 탈옥할 수 있는지
+    ///
+    /// 시드 기반 주사위라면 엔진이 주사위의 유일한 출처이므로 받지 않는다.
+    fn accepts_host_dice(&self) -> bool {
+        !self.is_seeded()
+    }
+}
+
+/// 게임 상태의 시드 기반 난수 생성기로 굴리는 기본 주사위
+pub struct SeededDice;
+
+impl DiceSource for SeededDice {
+    fn roll(&mut self, rng: &mut GameRng) -> Result<DicePair, GameError> {
+        Ok(DicePair(rng.random_range(1..=DICE_FACES), rng.random_range(1..=DICE_FACES)))
+    }
+
+    fn is_seeded(&self) -> bool {
+        true
+    }
+}
+
+/// 미리 정해 둔 순서대로 나오는 주사위 (테스트, 튜토리얼용)
+pub struct ScriptedDice {
+    queue: VecDeque<DicePair>,
+}
+
+impl ScriptedDice {
+    pub fn new(pairs: impl IntoIterator<Item = DicePair>) -> Self {
+        Self { queue: pairs.into_iter().collect() }
+    }
+}
+
+impl DiceSource for ScriptedDice {
+    fn roll(&mut self, _rng: &mut GameRng) -> Result<DicePair, GameError> {
+        self.queue.pop_front().ok_or_else(|| GameError::DiceUnavailable("Scripted dice are exhausted.".into()))
+    }
+}
+
+/// 호스트(JS)의 콜백이 굴리는 주사위 (`wasm` 기능)
+///
+/// 콜백은 `DicePair`의 직렬화 형식과 같이 `(첫째 눈 << 16) | 둘째 눈`의 정수를 반환해야 한다.
+#[cfg(feature = "wasm")]
+pub struct HostDice {
+    callback: js_sys::Function,
+}
+
+#[cfg(feature = "wasm")]
+impl HostDice {
+    pub fn new(callback: js_sys::Function) -> Self {
+        Self { callback }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl DiceSource for HostDice {
+    fn roll(&mut self, _rng: &mut GameRng) -> Result<DicePair, GameError> {
+        let value = self.callback.call0(&JsValue::NULL).map_err(|e| GameError::DiceUnavailable(format!("{:?}", e)))?;
+        let concatenated = value.as_f64().ok_or_else(|| GameError::DiceUnavailable("Host dice callback must return a number.".into()))? as u32;
+        Ok(DicePair((concatenated >> 16) as u16, concatenated as u16))
+    }
+}
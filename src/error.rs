@@ -0,0 +1,154 @@
+use std::fmt;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, GameSituation};
+
+/// 엔진의 모든 공개 API가 반환하는 오류
+///
+/// JS에는 `code`와 `message` 속성을 가진 `Error` 객체로 전달되므로,
+/// 호스트는 `code`로 분기하여 현지화된 메시지를 보여줄 수 있다.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameError {
+    /// 보드, 찬스 카드, 상수 JSON을 해석할 수 없음
+    InvalidJson(String),
+    /// 룰셋이 엔진이 요구하는 조건을 만족하지 않음
+    InvalidRuleset(String),
+    /// 세이브 파일을 읽거나 마이그레이션할 수 없음
+    InvalidSave(String),
+    /// 내장 메시지 카탈로그가 없는 로케일
+    UnknownLocale(String),
+    /// 리플레이 문서를 해석할 수 없음
+    InvalidReplay(String),
+    /// 리플레이를 다시 실행한 결과가 기록된 마지막 상태와 다름
+    ReplayMismatch { expected: String, actual: String },
+    /// 되돌리거나 다시 실행할 명령이 없음
+    UndoUnavailable(String),
+    /// 내장 봇 전략이 아닌 이름
+    UnknownStrategy(String),
+    /// 현재 상황에서 허용되지 않는 명령
+    InvalidSituation { command: Command, situation: GameSituation },
+    UnknownPlayer(u32),
+    /// 이미 파산하여 게임에서 빠진 플레이어
+    PlayerBankrupt(u32),
+    UnknownLoan { player_id: u32, loan_id: u32 },
+    /// 대출 한도(`LOAN_LIMIT_PERCENT`)를 넘는 대출
+    LoanLimitExceeded { requested: i64, limit: i64 },
+    /// 해당 이름 또는 종류의 타일이 보드에 없음
+    TileNotFound(String),
+    InvalidPosition(u32),
+    /// 아무도 소유하지 않은 부동산
+    UnownedProperty(String),
+    /// 타일에 더 지을 수 있는 건물이 없음
+    BuildingLimitReached(String),
+    /// 타일에 팔 건물이 없음
+    NoBuildingToSell(String),
+    /// 거래 제안이 올바르지 않거나 더 이상 성립하지 않음
+    InvalidTrade(String),
+    /// 경매가 없거나 입찰이 올바르지 않음
+    InvalidBid(String),
+    InvalidAmount(i64),
+    InsufficientFunds { required: i64, available: i64 },
+    /// 자산을 처분하면 부족액을 메울 수 있으므로 파산할 수 없음
+    NotBankrupt { deficit: i64, liquidation_value: i64 },
+    InvalidDice { first: u16, second: u16 },
+    /// 엔진이 굴린 주사위가 아니거나, 엔진이 주사위를 굴려야 하는데 호스트가 주사위를 넘김
+    UnrolledDice { first: u16, second: u16 },
+    /// 주사위 출처가 주사위를 굴리지 못함
+    DiceUnavailable(String),
+    EmptyChanceDeck,
+    /// Rhai 스크립트 실행 중 오류
+    ScriptError(String),
+    /// 스크립트가 반환한 값이 올바른 행동이 아님
+    InvalidScriptResult(String),
+}
+
+impl GameError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidJson(_) => "InvalidJson",
+            Self::InvalidRuleset(_) => "InvalidRuleset",
+            Self::InvalidSave(_) => "InvalidSave",
+            Self::UnknownLocale(_) => "UnknownLocale",
+            Self::InvalidReplay(_) => "InvalidReplay",
+            Self::ReplayMismatch { .. } => "ReplayMismatch",
+            Self::UndoUnavailable(_) => "UndoUnavailable",
+            Self::UnknownStrategy(_) => "UnknownStrategy",
+            Self::InvalidSituation { .. } => "InvalidSituation",
+            Self::UnknownPlayer(_) => "UnknownPlayer",
+            Self::PlayerBankrupt(_) => "PlayerBankrupt",
+            Self::UnknownLoan { .. } => "UnknownLoan",
+            Self::LoanLimitExceeded { .. } => "LoanLimitExceeded",
+            Self::TileNotFound(_) => "TileNotFound",
+            Self::InvalidPosition(_) => "InvalidPosition",
+            Self::UnownedProperty(_) => "UnownedProperty",
+            Self::BuildingLimitReached(_) => "BuildingLimitReached",
+            Self::NoBuildingToSell(_) => "NoBuildingToSell",
+            Self::InvalidTrade(_) => "InvalidTrade",
+            Self::InvalidBid(_) => "InvalidBid",
+            Self::InvalidAmount(_) => "InvalidAmount",
+            Self::InsufficientFunds { .. } => "InsufficientFunds",
+            Self::NotBankrupt { .. } => "NotBankrupt",
+            Self::InvalidDice { .. } => "InvalidDice",
+            Self::UnrolledDice { .. } => "UnrolledDice",
+            Self::DiceUnavailable(_) => "DiceUnavailable",
+            Self::EmptyChanceDeck => "EmptyChanceDeck",
+            Self::ScriptError(_) => "ScriptError",
+            Self::InvalidScriptResult(_) => "InvalidScriptResult",
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson(e) => write!(f, "Invalid JSON: {}", e),
+            Self::InvalidRuleset(e) => write!(f, "Invalid ruleset: {}", e),
+            Self::InvalidSave(e) => write!(f, "Invalid save: {}", e),
+            Self::UnknownLocale(locale) => write!(f, "Unknown locale '{}'.", locale),
+            Self::InvalidReplay(e) => write!(f, "Invalid replay: {}", e),
+            Self::ReplayMismatch { expected, actual } => write!(f, "The replay ended in state {} instead of {}.", actual, expected),
+            Self::UndoUnavailable(e) => write!(f, "Cannot undo: {}", e),
+            Self::UnknownStrategy(name) => write!(f, "Unknown bot strategy '{}'.", name),
+            Self::InvalidSituation { command, situation } => write!(f, "'{}' is not allowed while {:?}.", command.name(), situation),
+            Self::UnknownPlayer(pid) => write!(f, "Unknown player {}.", pid),
+            Self::PlayerBankrupt(pid) => write!(f, "Player {} is bankrupt.", pid),
+            Self::UnknownLoan { player_id, loan_id } => write!(f, "Player {} has no loan {}.", player_id, loan_id),
+            Self::LoanLimitExceeded { requested, limit } => write!(f, "Cannot borrow ${}: the loan limit is ${}.", requested, limit),
+            Self::TileNotFound(tile) => write!(f, "No '{}' tile on the board.", tile),
+            Self::InvalidPosition(pos) => write!(f, "Position {} is out of the board.", pos),
+            Self::UnownedProperty(name) => write!(f, "'{}' is not owned by anyone.", name),
+            Self::BuildingLimitReached(name) => write!(f, "No more buildings can be built on '{}'.", name),
+            Self::NoBuildingToSell(name) => write!(f, "There is no building to sell on '{}'.", name),
+            Self::InvalidTrade(e) => write!(f, "Invalid trade: {}", e),
+            Self::InvalidBid(e) => write!(f, "Invalid bid: {}", e),
+            Self::InvalidAmount(amount) => write!(f, "Invalid amount {}.", amount),
+            Self::InsufficientFunds { required, available } => write!(f, "Not enough money: ${} required, ${} available.", required, available),
+            Self::NotBankrupt { deficit, liquidation_value } => write!(f, "Assets worth ${} can cover the deficit of ${}.", liquidation_value, deficit),
+            Self::InvalidDice { first, second } => write!(f, "Invalid dice ({}, {}): each die must be between 1 and {}.", first, second, crate::DICE_FACES),
+            Self::UnrolledDice { first, second } => write!(f, "The dice ({}, {}) were not rolled by the engine.", first, second),
+            Self::DiceUnavailable(e) => write!(f, "Dice unavailable: {}", e),
+            Self::EmptyChanceDeck => write!(f, "There are no chance cards."),
+            Self::ScriptError(e) => write!(f, "Script error: {}", e),
+            Self::InvalidScriptResult(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<Box<rhai::EvalAltResult>> for GameError {
+    fn from(e: Box<rhai::EvalAltResult>) -> Self {
+        Self::ScriptError(e.to_string())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<GameError> for JsValue {
+    fn from(e: GameError) -> Self {
+        let error = js_sys::Error::new(&e.to_string());
+        _ = js_sys::Reflect::set(&error, &"code".into(), &e.code().into());
+        error.into()
+    }
+}
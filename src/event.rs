@@ -0,0 +1,113 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameEngine, MessageCatalog};
+
+/// 게임에서 일어난 사건
+///
+/// `type` 필드로 구분되며, `GameState.events`에 일어난 순서대로 쌓인다.
+/// `GameState.log`에는 같은 사건을 엔진의 메시지 카탈로그로 옮긴 문장이 함께 남는다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum GameEvent {
+    GameStarted,
+    TurnStarted { player_id: u32 },
+    TurnEnded { player_id: u32 },
+    GameEnded,
+    DiceRolled { player_id: u32, first: u16, second: u16 },
+    Moved { player_id: u32, from: u32, to: u32 },
+    /// 출발점을 지나 `cycles`번째 바퀴를 채움
+    PassedStart { player_id: u32, cycles: u32 },
+    Warped { player_id: u32, position: u32, tile_name: String },
+    Traveled { player_id: u32, position: u32, tile_name: String },
+    /// `owner_id`는 도착했을 때의 소유자
+    Landed { player_id: u32, tile_name: String, owner_id: Option<u32> },
+    Jailed { player_id: u32 },
+    Hospitalized { player_id: u32 },
+    SentToUniversity { player_id: u32 },
+    SentToElectricity { player_id: u32 },
+    CardDrawn { player_id: u32, card_id: String },
+    /// 행운 시험을 그만두고 쌓인 상금을 받음
+    LuckTestStopped { player_id: u32, amount: i64 },
+    /// `building`이 없으면 땅을 살지 묻는 중
+    PurchaseOffered { player_id: u32, tile_name: String, building: Option<String>, price: i64 },
+    CannotAfford { player_id: u32, tile_name: String, building: bool },
+    BuildingLimitReached { tile_name: String },
+    Bought { player_id: u32, tile_name: String, price: i64 },
+    Built { player_id: u32, tile_name: String, building: String, cost: i64 },
+    PurchaseDeclined { player_id: u32, tile_name: String, building: bool },
+    PaidTax { player_id: u32, amount: i64 },
+    PaidRent { player_id: u32, owner_id: u32, tile_name: String, amount: i64 },
+    NoRentDue { tile_name: String },
+    PaidGovernment { player_id: u32, amount: i64 },
+    PaidMarket { player_id: u32, amount: i64 },
+    PaidPlayer { player_id: u32, to_player_id: u32, amount: i64 },
+    /// 다른 플레이어들에게 한 명당 `amount`씩 지불
+    PaidToAll { player_id: u32, amount: i64 },
+    LoanTaken { player_id: u32, loan_id: u32, amount: i64 },
+    LoanRepaid { player_id: u32, loan_id: u32, amount: i64 },
+    /// 만기가 되어 강제로 상환된 대출
+    LoanCollected { player_id: u32, loan_id: u32, amount: i64 },
+    LoanDefaulted { player_id: u32 },
+    BuildingSold { player_id: u32, tile_name: String, amount: i64 },
+    Mortgaged { player_id: u32, tile_name: String, amount: i64 },
+    MortgageRedeemed { player_id: u32, tile_name: String, amount: i64 },
+    FinancialCrisisResolved { player_id: u32 },
+    /// `heir_id`가 없으면 부동산은 시장으로 돌아감
+    Bankrupt { player_id: u32, heir_id: Option<u32> },
+    TradeProposed { from: u32, to: u32 },
+    TradeAccepted { from: u32, to: u32 },
+    TradeRejected { from: u32, to: u32 },
+    TradeCountered { from: u32, to: u32 },
+    AuctionStarted { tile_name: String },
+    BidPlaced { player_id: u32, tile_name: String, amount: i64 },
+    BidPassed { player_id: u32 },
+    AuctionWon { player_id: u32, tile_name: String, amount: i64 },
+    /// 낙찰될 때 최고가를 낼 돈이 없어 무른 입찰
+    BidDropped { player_id: u32, tile_name: String, amount: i64 },
+    AuctionUnsold { tile_name: String },
+    /// 스크립트가 남긴 문장
+    Message { message: String },
+}
+
+impl fmt::Display for GameEvent {
+    /// 영어 메시지 카탈로그로 옮긴 문장
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", MessageCatalog::english().render(self))
+    }
+}
+
+impl GameEngine {
+    /// 사건을 기록하고 현재 로케일로 옮긴 문장을 로그에 남김
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        self.state.log.push(self.messages.render(&event));
+        self.state.events.push(event);
+    }
+
+    /// `index`번째부터의 사건
+    pub fn events_since(&self, index: usize) -> &[GameEvent] {
+        self.state.events.get(index..).unwrap_or_default()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 지금까지 일어난 사건의 수
+    pub fn get_event_count(&self) -> usize {
+        self.state.events.len()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// `index`번째부터의 사건 (`GameEvent` 배열의 JSON)
+    ///
+    /// 호스트는 지금까지 받은 사건의 수를 넘겨 새 사건만 받을 수 있다.
+    pub fn get_events_since(&self, index: usize) -> String {
+        serde_json::to_string(self.events_since(index)).unwrap()
+    }
+}
@@ -1,13 +1,57 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use rhai::{Engine, Map, Scope};
+use rand::Rng;
 use serde::{Serialize, Deserialize};
-use std::{cmp::min, collections::HashMap, ops::{Add, AddAssign, Sub, SubAssign}};
-
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+use std::{collections::HashMap, ops::{Add, AddAssign, Sub, SubAssign}};
+
+mod action;
+mod auction;
+mod bot;
+mod bot_script;
+mod building;
+mod crisis;
+mod decision;
+mod dice;
+mod error;
+mod event;
+mod loan;
+mod message;
+mod purchase;
+mod ranking;
+mod rent;
+mod replay;
+mod rng;
+mod save;
+mod simulation;
+mod situation;
+#[cfg(test)]
+mod testing;
+mod trade;
+mod undo;
+
+pub use action::{ChanceAction, CycleResult, Payment, TurnAction};
+pub use auction::Auction;
+pub use bot::{BotAction, BuiltinStrategy, ConservativeStrategy, GreedyStrategy, PlayerStrategy, RandomStrategy};
+pub use bot_script::ScriptStrategy;
+pub use building::BuildingTier;
+pub use crisis::{Creditor, LiquidationOption};
+pub use decision::{ChanceOption, PendingDecision};
+pub use dice::{DiceSource, ScriptedDice, SeededDice, DICE_FACES};
+#[cfg(feature = "wasm")]
+pub use dice::HostDice;
+pub use error::GameError;
+pub use event::GameEvent;
+pub use message::MessageCatalog;
+pub use purchase::Purchase;
+pub use ranking::{EndReason, RankingEntry};
+pub use replay::{Replay, ReplayStep};
+pub use rng::GameRng;
+pub use save::{SaveFile, SCHEMA_VERSION};
+pub use simulation::{simulate, ChanceCardStats, FailedGame, Scripts, SeatStats, SeatStrategy, SimulationConfig, SimulationReport, TileStats, TurnStats};
+pub use situation::Command;
+pub use trade::{Trade, TradeOffer, TradeSide};
+pub use undo::UndoPolicy;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Tile {
@@ -22,6 +66,12 @@ pub struct Tile {
     pub is_coastal: bool,
     #[serde(default)]
     pub is_megacity: bool,
+    /// 건물 수별 통행료 (`rent[건물 수]`), 비어 있으면 땅값으로 계산
+    #[serde(default)]
+    pub rent: Vec<i64>,
+    /// 지을 수 있는 건물 단계, 비어 있으면 `building_cost`와 `MAX_BUILDINGS`로 정함
+    #[serde(default)]
+    pub buildings: Vec<BuildingTier>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -29,9 +79,12 @@ pub struct ChanceCard {
     pub title: String,
     pub descriptoin: String,
     pub instruction: String,
+    /// 플레이어가 고를 수 있는 `payload` 후보 (비어 있으면 고를 것이 없음)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payload_options: Vec<serde_json::Value>,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[repr(u8)]
 pub enum EducationStatus {
     NotYet = 0u8, Undergraduated = 1u8, Graduated = 2u8
@@ -47,8 +100,8 @@ impl EducationStatus {
     }
 }
 
-#[wasm_bindgen]
-#[derive(Serialize, Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
 pub struct TicketCount {
     pub free_hospital: u32,
     pub free_property: u32,
@@ -179,25 +232,55 @@ impl Default for TicketCount {
 }
 
 
-#[derive(Serialize, Clone, Debug)]
+/// 대출
+///
+/// 대출 규칙은 `consts`로 설정한다.
+/// - `LOAN_INTEREST_PERCENT` (기본 5): 한 기간마다 남은 대출금에 붙는 이자
+/// - `LOAN_TERM` (기본 4): 만기까지의 기간 수
+/// - `LOAN_TERM_IN_CYCLES` (기본 0): 1이면 바퀴를 채울 때마다, 0이면 빌린 플레이어의 턴이 끝나고 다음 플레이어로 넘어갈 때마다 한 기간이 지남 (더블로 얻은 턴은 세지 않음)
+/// - `LOAN_LIMIT_PERCENT` (기본 0): 남은 대출 총액이 자산(순자산 + 남은 대출)의 이 비율을 넘을 수 없음, 0이면 제한 없음
+///
+/// 만기가 된 대출은 그다음 턴이 끝날 때 강제로 상환되며, 현금이 모자라면 재정 위기에 빠진다.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Loan {
+    pub id: u32,
+    /// 이자를 포함한 남은 대출금
+    pub amount: i64,
+    /// 만기까지 남은 기간 (0이면 만기)
+    pub remaining_turns: u32,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct Ownership {
+    pub owner_id: u32,
+    pub buildings: u32,
+    #[serde(default)]
+    pub mortgaged: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Player {
     pub id: u32,
     pub position: u32,
     pub money: i64,
-    pub remaining_loans: Vec<(u32, i64, u32)>,
+    pub remaining_loans: Vec<Loan>,
     pub education_status: EducationStatus,
     pub cycles: u32,
     pub remaining_jail_turns: u32,
     pub tickets_count: TicketCount,
+    #[serde(default)]
+    pub bankrupt: bool,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GameState {
     board: Vec<Tile>,
     chance_cards_inventory: HashMap<String, ChanceCard>,
     players: Vec<Player>,
-    properties: HashMap<String, (u32,u32)>,
+    properties: HashMap<String, Ownership>,
     log: Vec<String>,
+    #[serde(default)]
+    events: Vec<GameEvent>,
     current_turn_idx: usize,
     government_income: i64,
     dice_double: bool,
@@ -206,9 +289,63 @@ pub struct GameState {
     consts: HashMap<String, u32>,
     pending_ticket: TicketCount,
     luck_test_cache: i64,
+    rng: GameRng,
+    #[serde(default)]
+    crisis_creditor: Option<Creditor>,
+    /// 지금까지 끝난 턴의 수
+    #[serde(default)]
+    turn_count: u32,
+    /// 파산한 순서대로의 플레이어 id
+    #[serde(default)]
+    eliminations: Vec<u32>,
+    #[serde(default)]
+    end_reason: Option<EndReason>,
+    #[serde(default)]
+    pending_trade: Option<Trade>,
+    #[serde(default)]
+    auction: Option<Auction>,
+    #[serde(default)]
+    pending_purchase: Option<Purchase>,
+    /// 엔진이 굴렸지만 아직 움직이거나 탈옥하는 데 쓰지 않은 주사위
+    #[serde(default)]
+    rolled_dice: Option<DicePair>,
 }
 
-#[wasm_bindgen]
+impl GameState {
+    pub fn board(&self) -> &[Tile] {
+        &self.board
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// 현재 차례인 플레이어
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current_turn_idx]
+    }
+
+    /// 타일 이름별 소유 정보
+    pub fn properties(&self) -> &HashMap<String, Ownership> {
+        &self.properties
+    }
+
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    pub fn government_income(&self) -> i64 {
+        self.government_income
+    }
+
+    /// 지금까지 끝난 턴의 수
+    pub fn turn_count(&self) -> u32 {
+        self.turn_count
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum GameSituation {
     InAction,
     PendingBuyResponse,
@@ -219,21 +356,43 @@ pub enum GameSituation {
     PendingTryToJailbreakResponse,
     PendingGetRandomChanceCardResponse,
     PendingCheckChanceCardResponse,
+    PendingTradeResponse,
+    PendingAuction,
+    /// 주사위를 굴렸고 그 주사위로 움직이기를 기다리는 중
+    PendingMoveResponse,
+    /// 감옥에서 주사위를 굴렸고 그 주사위로 탈옥을 시도하기를 기다리는 중
+    PendingJailbreakByDicesResponse,
     EndTurn,
     EndGame
 }
 
-#[wasm_bindgen]
+/// 새로고침 후에도 게임을 그대로 이어갈 수 있도록 엔진의 모든 가변 상태를 담는 스냅샷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GameSnapshot {
+    state: GameState,
+    salary: i64,
+    building_cost: i64,
+    now: GameSituation,
+    pending_chance_card_id: Option<String>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct GameEngine {
     pub(crate) engine: Engine,
     pub(crate)state: GameState,
     salary: i64,
     building_cost: i64,
     now: GameSituation,
-    pending_chance_card_id: Option<String>
+    pending_chance_card_id: Option<String>,
+    dice_source: Box<dyn DiceSource>,
+    messages: MessageCatalog,
+    recorder: replay::Recorder,
+    undo: undo::UndoHistory,
+    /// 봇이 조종하는 플레이어 id별 전략
+    bots: HashMap<u32, Box<dyn PlayerStrategy>>,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct DicePair(u16, u16);
 
@@ -262,14 +421,105 @@ impl<'de> Deserialize<'de> for DicePair {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl DicePair {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(first: u16, second: u16) -> Result<DicePair, GameError> {
+        let dices = Self(first, second);
+        dices.validate()?;
+        Ok(dices)
+    }
+
+    pub fn first(&self) -> u16 {
+        self.0
+    }
+
+    pub fn second(&self) -> u16 {
+        self.1
+    }
+
     pub fn is_double(&self) -> bool {
         self.0 == self.1
     }
+
+    /// 두 눈이 모두 1 이상 `DICE_FACES` 이하인지 확인
+    pub fn validate(&self) -> Result<(), GameError> {
+        if (1..=DICE_FACES).contains(&self.0) && (1..=DICE_FACES).contains(&self.1) {
+            Ok(())
+        } else {
+            Err(GameError::InvalidDice { first: self.0, second: self.1 })
+        }
+    }
 }
 
 
+impl GameEngine {
+    /// 이미 해석한 보드, 찬스 카드, 상수로 엔진을 만듦 (`new` 참고)
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_rules(board: Vec<Tile>, chance_cards_inventory: HashMap<String, ChanceCard>, consts: HashMap<String, u32>, players_count: usize, initial_money: i64, salary: i64, building_cost: i64, seed: Option<u64>) -> Result<GameEngine, GameError> {
+        if board.is_empty() {
+            return Err(GameError::InvalidRuleset("The board has no tiles.".into()));
+        }
+        let state = GameState {
+            board,
+            chance_cards_inventory,
+            players: (0..players_count).map(|i| Player { id: (i+1) as u32, position: 0, money: initial_money, remaining_loans: Vec::new(), education_status: EducationStatus::NotYet, cycles: 0, remaining_jail_turns: 0, tickets_count: TicketCount::default(), bankrupt: false }).collect(),
+            properties: HashMap::new(),
+            log: vec![GameEvent::GameStarted.to_string()],
+            events: vec![GameEvent::GameStarted],
+            current_turn_idx: 0,
+            government_income: 0,
+            dice_double: false,
+            pandemic_counter: 0,
+            catastrophe_counter: 0,
+            consts,
+            pending_ticket: TicketCount::zero(),
+            luck_test_cache: -1,
+            rng: GameRng::new(seed.unwrap_or_else(rand::random)),
+            crisis_creditor: None,
+            turn_count: 0,
+            eliminations: Vec::new(),
+            end_reason: None,
+            pending_trade: None,
+            auction: None,
+            pending_purchase: None,
+            rolled_dice: None,
+        };
+        Ok(Self::from_parts(state, salary, building_cost, GameSituation::PendingRollResponse, None))
+    }
+
+    /// `get_snapshot_as_json`과 같은 형식의 세이브 파일로부터 엔진을 복원
+    pub fn from_save_file(save: SaveFile) -> Result<GameEngine, GameError> {
+        let snapshot = save.into_current_snapshot()?;
+        Ok(Self::from_parts(snapshot.state, snapshot.salary, snapshot.building_cost, snapshot.now, snapshot.pending_chance_card_id))
+    }
+
+    /// 임의의 `DiceSource` 구현으로 주사위 출처를 교체
+    pub fn set_dice_source(&mut self, dice_source: Box<dyn DiceSource>) {
+        self.dice_source = dice_source;
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// 현재 상황에서 호출할 수 있는 명령
+    ///
+    /// 주사위 출처가 호스트의 주사위를 받는다면 주사위를 굴리지 않고 움직이거나 탈옥하는 명령도 포함한다.
+    pub fn legal_commands(&self) -> Vec<Command> {
+        let mut commands = self.now.legal_commands();
+        if self.dice_source.accepts_host_dice() {
+            commands.extend_from_slice(self.now.host_dice_commands());
+        }
+        commands
+    }
+
+    /// 스키마 버전, 엔진 버전, 룰셋 해시를 포함한 세이브 파일
+    pub fn save_file(&self) -> SaveFile {
+        SaveFile::new(&self.snapshot())
+    }
+}
+
 fn map_pair<T, R, F>(pair: (T, T), f: F) -> (R, R)
 where F: Fn(T) -> R {
     (f(pair.0), f(pair.1))
@@ -288,7 +538,7 @@ where
 
 
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GameEngine {
     fn round(x: i64, n: i64) -> i64 {
         let rem = x % n;
@@ -299,26 +549,17 @@ impl GameEngine {
         }
     }
 
-    #[wasm_bindgen(constructor)]
-    pub fn new(board_json: &str, chance_cards_json: &str, consts_json: &str, players_count: usize, initial_money: i64, salary: i64, building_cost: i64) -> Result<GameEngine, String> {
-        let board: Vec<Tile> = serde_json::from_str(board_json).map_err(|e| e.to_string())?;
-        let chance_cards_inventory: HashMap<String, ChanceCard> = serde_json::from_str(chance_cards_json).map_err(|e| e.to_string())?;
-        let consts: HashMap<String, u32> = serde_json::from_str(consts_json).map_err(|e| e.to_string())?;
-        let state = GameState {
-            board,
-            chance_cards_inventory,
-            players: (0..players_count).map(|i| Player { id: (i+1) as u32, position: 0, money: initial_money, remaining_loans: Vec::new(), education_status: EducationStatus::NotYet, cycles: 0, remaining_jail_turns: 0, tickets_count: TicketCount::default() }).collect(),
-            properties: HashMap::new(),
-            log: vec!["Game started!".into()],
-            current_turn_idx: 0,
-            government_income: 0,
-            dice_double: false,
-            pandemic_counter: 0,
-            catastrophe_counter: 0,
-            consts,
-            pending_ticket: TicketCount::zero(),
-            luck_test_cache: -1,
-        };
+    /// `seed`를 생략하면 무작위 시드를 사용하며, 사용된 시드는 `get_seed`로 확인할 수 있다
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(board_json: &str, chance_cards_json: &str, consts_json: &str, players_count: usize, initial_money: i64, salary: i64, building_cost: i64, seed: Option<u64>) -> Result<GameEngine, GameError> {
+        let board: Vec<Tile> = serde_json::from_str(board_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        let chance_cards_inventory: HashMap<String, ChanceCard> = serde_json::from_str(chance_cards_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        let consts: HashMap<String, u32> = serde_json::from_str(consts_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        Self::with_rules(board, chance_cards_inventory, consts, players_count, initial_money, salary, building_cost, seed)
+    }
+
+    fn from_parts(state: GameState, salary: i64, building_cost: i64, now: GameSituation, pending_chance_card_id: Option<String>) -> GameEngine {
         let mut engine = Engine::new();
 
         // Rhai가 Rust 객체를 사용할 수 있도록 등록
@@ -375,11 +616,49 @@ impl GameEngine {
             coastal_cities
         });
 
-        Ok(Self {
-            engine, state, salary, building_cost,
-            pending_chance_card_id: None,
-            now: GameSituation::PendingRollResponse
-        })
+        let messages = MessageCatalog::default();
+        let recorder = replay::Recorder::new(&GameSnapshot { state: state.clone(), salary, building_cost, now, pending_chance_card_id: pending_chance_card_id.clone() }, &messages);
+        Self {
+            engine, state, salary, building_cost, now, pending_chance_card_id,
+            dice_source: Box::new(SeededDice),
+            messages,
+            recorder,
+            undo: undo::UndoHistory::default(),
+            bots: HashMap::new(),
+        }
+    }
+
+    /// `get_snapshot_as_json`으로 저장한 세이브 파일로부터 엔진을 복원
+    ///
+    /// 이전 스키마 버전의 세이브는 현재 버전으로 마이그레이션한 뒤 불러온다.
+    pub fn from_snapshot(snapshot_json: &str) -> Result<GameEngine, GameError> {
+        Self::from_save_file(SaveFile::parse(snapshot_json)?)
+    }
+
+    /// 현재 엔진의 상태를 스냅샷으로 통째로 교체
+    pub fn load_state_from_json(&mut self, snapshot_json: &str) -> Result<(), GameError> {
+        let messages = std::mem::take(&mut self.messages);
+        let policy = self.get_undo_policy();
+        let bots = std::mem::take(&mut self.bots);
+        *self = Self::from_snapshot(snapshot_json)?;
+        self.messages = messages;
+        self.bots = bots;
+        self.undo = undo::UndoHistory::new(policy);
+        self.start_recording();
+        Ok(())
+    }
+
+    /// 보드에서 `tile_type` 종류의 첫 타일 위치
+    fn find_tile_of_type(board: &[Tile], tile_type: &str) -> Result<usize, GameError> {
+        board.iter().position(|t| t.tile_type == tile_type).ok_or_else(|| GameError::TileNotFound(tile_type.into()))
+    }
+
+    fn check_position(board: &[Tile], position: u32) -> Result<(), GameError> {
+        if (position as usize) < board.len() {
+            Ok(())
+        } else {
+            Err(GameError::InvalidPosition(position))
+        }
     }
 
     fn get_coastal_cities(board: &Vec<Tile>) -> Vec<String> {
@@ -391,19 +670,19 @@ impl GameEngine {
         }).collect()
     }
 
-    fn get_owned_properties(props: &HashMap<String, (u32, u32)>, player_id: u32) -> (HashMap<String, u32>, HashMap<String, u32>) {
-        let partition: (Vec<_>, Vec<_>) = props.iter().partition(|&(_, (owner_id, _))| {
-            *owner_id == player_id
+    fn get_owned_properties(props: &HashMap<String, Ownership>, player_id: u32) -> (HashMap<String, u32>, HashMap<String, u32>) {
+        let partition: (Vec<_>, Vec<_>) = props.iter().partition(|&(_, ownership)| {
+            ownership.owner_id == player_id
         });
         let partition_iterators = (partition.0.iter(), partition.1.iter());
-        let mapped_partition = map_partition::<&(&String, &(u32, u32)),_,(String, u32),_,HashMap<_, _>>(partition_iterators, |&(name, &(_, owned_amount))| {
-            (name.clone(), owned_amount)
+        let mapped_partition = map_partition::<&(&String, &Ownership),_,(String, u32),_,HashMap<_, _>>(partition_iterators, |&(name, ownership)| {
+            (name.clone(), ownership.buildings)
         });
         mapped_partition
     }
 
 
-    fn try_run_turn_script(&mut self, script_action: &str, dices: Option<DicePair>, script_cycle: &str, to_use_ticket: i64) -> Result<(), String> {
+    fn try_run_turn_script(&mut self, script_action: &str, dices: Option<DicePair>, script_cycle: &str, to_use_ticket: i64) -> Result<(), GameError> {
         self.now = GameSituation::InAction;
         let mut scope = Scope::new();
         let player_index = self.state.current_turn_idx;
@@ -419,14 +698,16 @@ impl GameEngine {
         let new_pos = (player.position + (dices.0 + dices.1) as u32) % self.state.board.len() as u32;
         let tile = self.state.board[new_pos as usize].clone();
         let is_owned = self.state.properties.contains_key(&tile.name);
-        let (owner_id, owned_amount) = if let Some(&(owner_id, owned_amount)) = self.state.properties.get(&tile.name) {
-            (Some(owner_id), Some(owned_amount))
+        let (owner_id, owned_amount) = if let Some(ownership) = self.state.properties.get(&tile.name) {
+            (Some(ownership.owner_id), Some(ownership.buildings))
         } else {
             (None, None)
         };
 
         // 주사위가 더블일 때
         if dices != DicePair(0, 0) {
+            self.emit(GameEvent::DiceRolled { player_id: player.id, first: dices.0, second: dices.1 });
+            self.emit(GameEvent::Moved { player_id: player.id, from: old_pos, to: new_pos });
             if dices.0 == dices.1 {
                 self.state.dice_double = true;
             } else {
@@ -440,113 +721,76 @@ impl GameEngine {
             self.trigger_cycle(script_cycle)?;
         }
 
+        let tile_name = tile.name.clone();
+        let max_buildings = self.max_buildings(&tile);
         scope.push("tile", tile);
         scope.push("is_owned", is_owned);
         scope.push("owner_id", owner_id);
         scope.push("owned_amount", owned_amount);
         scope.push("building_cost", self.building_cost);
-        scope.push_constant("MAX_BUILDINGS", if let Some(&max_buildings) = self.state.consts.get("MAX_BUILDINGS") && max_buildings > 0 {
-            max_buildings
-        } else {
-            1
-        });
+        scope.push("rent", self.rent_of(&tile_name).map(|(_, rent)| rent).unwrap_or(0));
+        // 이 타일에 지을 수 있는 건물 수와 다음 건물의 비용 (더 지을 수 없으면 0)
+        scope.push_constant("MAX_BUILDINGS", max_buildings);
+        scope.push("next_building_cost", self.next_building_tier(&tile_name).map(|tier| tier.cost).unwrap_or(0));
 
         scope.push("to_use_ticket", to_use_ticket);
         
         let tickets = self.state.players[player_index].tickets_count.clone();
         scope.push("tickets", tickets);
 
-        let result: Map = self.engine.eval_with_scope(&mut scope, script_action).map_err(|e| e.to_string())?;
+        let result: Map = self.engine.eval_with_scope(&mut scope, script_action)?;
 
-        let action_type = result["type"].clone().into_string().unwrap();
-        match action_type.as_str() {
-            "PromptBuy" => {
-                let name = result["tile_name"].clone().into_string().unwrap();
-                let price = result["price"].clone().as_int().unwrap();
-                self.state.log.push(format!("Landed on {}'{}'.", if let Some(_) = owner_id { "" } else { "unowned " }, name));
-                // 구매 로직
-                let player_mut = &mut self.state.players[player_index];
-                
-                let free_flag = result["free_flag"].clone().as_bool().unwrap_or(false);
-                let ticket_flag = result["ticket_flag"].clone().as_bool().unwrap_or(false);
-                if !free_flag && !ticket_flag {
-                    player_mut.money -= price;
-                }
-
-                if player_mut.money >= self.building_cost {
-                    self.state.log.push(format!("Buy {} building for ${}?", if let Some(_) = owner_id { "one more" } else { "a" },self.building_cost));
-                    self.now = GameSituation::PendingBuyResponse;                  
+        match TurnAction::from_map(&result)? {
+            TurnAction::PromptBuy { tile_name: name, price, free_flag, ticket_flag } => {
+                self.emit(GameEvent::Landed { player_id: player.id, tile_name: name.clone(), owner_id });
+                // 무료이거나 티켓을 쓰면 땅값을 받지 않으며, 돈은 플레이어가 구매를 받아들일 때 나감
+                let land_price = if owner_id.is_some() {
+                    None
+                } else if free_flag || ticket_flag {
+                    Some(0)
                 } else {
-                    self.state.log.push("Not enough money to buy.".into());
-                }
+                    Some(price)
+                };
+                self.offer_purchase(&name, land_price);
+            },
+            TurnAction::PayRent => {
+                self.pay_rent(player_index, &tile_name)?;
             },
-            "PayTax" => {
-                let amount = result["amount"].clone().as_int().unwrap();
+            TurnAction::PayTax { amount } => {
                 self.state.players[player_index].money -= amount;
                 self.state.government_income += amount;
-                self.state.log.push(format!("Player {} Paid ${} in taxes.", self.state.players[player_index].id, amount));
+                self.emit(GameEvent::PaidTax { player_id: player.id, amount });
 
                 if self.state.players[player_index].money < 0 {
-                    self.prompt_financial_crisis();
+                    self.prompt_financial_crisis(Creditor::Government);
                 }
             },
-            "Imprison" => {
-                self.state.log.push("Imprisoned!".into());
+            TurnAction::Imprison => {
+                self.emit(GameEvent::Jailed { player_id: player.id });
                 self.now = GameSituation::EndTurn;
             },
-            "WarpToPosition" => {
-                let dest = result["position"].clone().as_int().unwrap() as u32;
+            TurnAction::WarpToPosition { position: dest } => {
+                Self::check_position(&self.state.board, dest)?;
                 self.state.players[player_index].position = dest;
-                self.state.log.push(format!("Warped to {}!", self.state.board[dest as usize].name));
+                self.emit(GameEvent::Warped { player_id: player.id, position: dest, tile_name: self.state.board[dest as usize].name.clone() });
                 self.now = GameSituation::EndTurn;
                 return Ok(()); // 이동 로직을 건너뛰기 위해 여기서 종료
             },
-            "PayTo" => {
-                let government_amount = if let Ok(amount) = result["gov_amount"].clone().as_int() {
-                    Some(amount)
-                } else { None };
-                let market_amount = if let Ok(amount) = result["market_amount"].clone().as_int() {
-                    Some(amount)
-                } else { None };
-                let to_player = if let Ok(amount) = result["player_amount"].clone().as_int() && let Ok(pid) = result["to_player_id"].clone().as_int() {
-                    Some((amount, pid as u32))
-                } else { None };
-                let payer_id = self.state.players[player_index].id;
-
-                let message = result["message"].clone().into_string().unwrap();
-                self.state.log.push(message);
-
-                if let Some(amount) = government_amount {
-                    self.state.government_income += amount;
-                    self.state.players[player_index].money -= amount;
-                    self.state.log.push(format!("\tPlayer {} Paid ${} to the government.", payer_id, amount));
-                }
-
-                if let Some(amount) = market_amount {
-                    self.state.players[player_index].money -= amount;
-                    self.state.log.push(format!("\tPlayer {} Paid ${} to the market.", payer_id, amount));
-                }
-
-                if let Some((amount, pid)) = to_player {
-                    let to_player = self.state.players.iter_mut().find(|player| player.id == pid);
-                    if let Some(to_player_mut) = to_player {
-                        to_player_mut.money += amount;
-                        self.state.players[player_index].money -= amount;
-                        self.state.log.push(format!("\tPlayer {} Paid ${} to Player {}.", payer_id, amount, pid));
-                    }
-                }
+            TurnAction::PayTo(payment) => {
+                let creditor = payment.creditor();
+                self.pay(player_index, payment);
 
                 if self.state.players[player_index].money < 0 {
-                    self.prompt_financial_crisis();
+                    self.prompt_financial_crisis(creditor);
                 }
             },
-            "PayToAll" => {
-                let amount = result["amount"].clone().as_int().unwrap();
+            TurnAction::PayToAll { amount } => {
                 let payer_id = self.state.players[player_index].id;
-                let players_count = (self.state.players.len()) as u32;
+                // 파산한 플레이어는 받지 않으므로, 총액은 남은 다른 플레이어들과 정부 몫
+                let players_count = self.state.players.iter().filter(|player| !player.bankrupt).count() as u32;
 
                 // ... (payer_id를 제외한 모든 플레이어들과 정부에게 amount씩 더하고, payer에게서는 총액을 빼는 로직) ...
-                for player in self.state.players.iter_mut() {
+                for player in self.state.players.iter_mut().filter(|player| !player.bankrupt) {
                     if player.id == payer_id {
                         player.money -= amount * players_count as i64;
                     } else {
@@ -555,16 +799,15 @@ impl GameEngine {
                 }
                 self.state.government_income += amount;
 
-                self.state.log.push(format!("Paid ${} per each to other players.", amount));
+                self.emit(GameEvent::PaidToAll { player_id: payer_id, amount });
 
                 if self.state.players[player_index].money < 0 {
-                    self.prompt_financial_crisis();
+                    self.prompt_financial_crisis(Creditor::Market);
                 }
             },
-            "AllEarn" => {
+            TurnAction::AllEarn { amount_unit } => {
                 let trigger_id = self.state.players[player_index].id;
-                let amount_unit = result["amount_unit"].clone().as_int().unwrap();
-                self.state.players.iter_mut().for_each(move |player| {
+                self.state.players.iter_mut().filter(|player| !player.bankrupt).for_each(move |player| {
                     let ratio = if player.id == trigger_id {
                         2
                     } else { 1 };
@@ -573,46 +816,42 @@ impl GameEngine {
                 self.state.government_income += amount_unit;
                 self.now = GameSituation::EndTurn;
             }
-            "PromptLuckTest" => {
+            TurnAction::PromptLuckTest => {
                 self.now = GameSituation::PendingLuckTestResponse;
             },
-            "PromptFinancialCrisis" => {
-                let cost = result["cost"].clone().as_int().unwrap();
+            TurnAction::PromptFinancialCrisis { cost } => {
                 let player_mut = &mut self.state.players[player_index];
                 player_mut.money -= cost;
-                self.prompt_financial_crisis();
+                self.prompt_financial_crisis(Creditor::Market);
             },
-            "Educate" => {
+            TurnAction::Educate => {
                 Self::educate(&mut self.state.players[player_index]);
                 self.now = GameSituation::EndTurn;
             },
-            "MedicalCare" => {
-                let free = result["free"].clone().as_bool().unwrap();
-                _ = self.medical_care(free);
+            TurnAction::MedicalCare { free } => {
+                self.medical_care(free)?;
             },
-            "Concert" => {
-                let price = result["price"].clone().as_int().unwrap();
+            TurnAction::Concert { price } => {
                 self.state.players[player_index].money -= price;
                 self.state.government_income += price / 10;
 
                 if self.state.players[player_index].money < 0 {
-                    self.prompt_financial_crisis();
+                    self.prompt_financial_crisis(Creditor::Market);
                 }
             },
-            "GetRandomChanceCard" => {
+            TurnAction::GetRandomChanceCard => {
                 self.now = GameSituation::PendingGetRandomChanceCardResponse;
             },
-            "PromptTicket" => {
-                let kind = result["kind"].clone().into_string().unwrap();
+            TurnAction::PromptTicket { kind } => {
                 self.state.pending_ticket += TicketCount::get_one_ticket(kind.as_str());
                 if self.state.pending_ticket != TicketCount::zero() {
                     self.now = GameSituation::PendingUseTicketResponse
                 }
             }
-            _ => { // Log
-                let message = result["message"].clone().into_string().unwrap();
-                self.state.log.push(message);
-            }
+            TurnAction::Log { message } => {
+                self.emit(GameEvent::Message { message });
+            },
+            TurnAction::Other => unreachable!("TurnAction::from_map resolves unknown types into Log"),
         }
 
         if let GameSituation::InAction = self.now {
@@ -624,70 +863,89 @@ impl GameEngine {
         Ok(())
     }
 
-    #[wasm_bindgen]
-    pub fn use_ticket(&mut self, to_use: TicketCount, script_action: &str, script_cycle: &str) -> Result<(), String> {
-        if let GameSituation::PendingUseTicketResponse = self.now {
-            let player_index = self.state.current_turn_idx;
-            let position = self.state.players[player_index].position;
-            match self.state.board[position as usize].tile_type.as_str() {
-                "LuckTest" => {
-                    self.now = GameSituation::PendingLuckTestResponse;
-                    if to_use.double_lotto > 0 {
-                        self.state.players[player_index].tickets_count.double_lotto -= 1;
-                        self.luck_test(true);
-                    }
-                },
-                "Jail" => {
-                    if to_use.release_from_jail > 0 {
-                        self.state.players[player_index].tickets_count.release_from_jail -= 1;
-                        self.state.players[player_index].remaining_jail_turns = 0;
-                    }
-                    self.now = GameSituation::EndTurn;
-                },
-                "Hospital" => {
-                    if to_use.free_hospital > 0 {
-                        self.state.players[player_index].tickets_count.free_hospital -= 1;
-                    }
-                    _ = self.medical_care(to_use.free_hospital > 0);
-                    self.now = GameSituation::EndTurn;
-                },
-                "Property" | "IndustrialComplex" => {
-                    let to_use_ticket = if to_use.free_property > 0 {
-                        self.state.players[player_index].tickets_count.free_property -= 1;
-                        1_i64
-                    } else {
-                        -1_i64
-                    };
-                    let result = self.try_run_turn_script(script_action, None, script_cycle, to_use_ticket);
-                    if let Err(e) = result {
-                        return Err(e);
-                    }
+    pub fn use_ticket(&mut self, to_use: TicketCount, script_action: &str, script_cycle: &str) -> Result<(), GameError> {
+        self.expect(Command::UseTicket)?;
+        let step = ReplayStep::UseTicket { to_use, script_action: self.intern_script(script_action), script_cycle: self.intern_script(script_cycle) };
+        self.record(step, |game| game.apply_tickets(to_use, script_action, script_cycle))
+    }
+
+    /// 제안된 티켓을 쓰고 그에 따라 턴을 마저 진행
+    fn apply_tickets(&mut self, to_use: TicketCount, script_action: &str, script_cycle: &str) -> Result<(), GameError> {
+        let player_index = self.state.current_turn_idx;
+        let position = self.state.players[player_index].position;
+        match self.state.board[position as usize].tile_type.as_str() {
+            "LuckTest" => {
+                self.now = GameSituation::PendingLuckTestResponse;
+                if to_use.double_lotto > 0 {
+                    self.state.players[player_index].tickets_count.double_lotto -= 1;
+                    self.draw_luck_test(true);
                 }
-                "Tax" => {
-                    let to_use_ticket = if to_use.no_tax > 0 {
-                        self.state.players[player_index].tickets_count.no_tax -= 1;
-                        1_i64
-                    } else {
-                        -1_i64
-                    };
-                    let result = self.try_run_turn_script(script_action, None, script_cycle, to_use_ticket);
-                    if let Err(e) = result {
-                        return Err(e);
-                    }
-                },
-                _ => {
-                    return Ok(());
+            },
+            "Jail" => {
+                if to_use.release_from_jail > 0 {
+                    self.state.players[player_index].tickets_count.release_from_jail -= 1;
+                    self.state.players[player_index].remaining_jail_turns = 0;
+                }
+                self.now = GameSituation::EndTurn;
+            },
+            "Hospital" => {
+                if to_use.free_hospital > 0 {
+                    self.state.players[player_index].tickets_count.free_hospital -= 1;
                 }
+                self.medical_care(to_use.free_hospital > 0)?;
+                self.now = GameSituation::EndTurn;
+            },
+            "Property" | "IndustrialComplex" => {
+                let to_use_ticket = if to_use.free_property > 0 {
+                    self.state.players[player_index].tickets_count.free_property -= 1;
+                    1_i64
+                } else {
+                    -1_i64
+                };
+                self.try_run_turn_script(script_action, None, script_cycle, to_use_ticket)?;
+            }
+            "Tax" => {
+                let to_use_ticket = if to_use.no_tax > 0 {
+                    self.state.players[player_index].tickets_count.no_tax -= 1;
+                    1_i64
+                } else {
+                    -1_i64
+                };
+                self.try_run_turn_script(script_action, None, script_cycle, to_use_ticket)?;
+            },
+            _ => {
+                return Ok(());
             }
         }
         Ok(())
+    }
 
+    pub fn luck_test(&mut self, init_double_lotto: bool) -> Result<(), GameError> {
+        self.expect(Command::LuckTest)?;
+        self.record(ReplayStep::LuckTest { init_double_lotto }, |game| {
+            game.draw_luck_test(init_double_lotto);
+            Ok(())
+        })
     }
 
-    #[wasm_bindgen]
-    pub fn luck_test(&mut self, init_double_lotto: bool) {
-        if let GameSituation::PendingLuckTestResponse =  self.now && self.state.luck_test_cache != 0_i64 {
-            let randvar = rand::random_bool(1.0/10.0);
+    /// 행운 시험을 그만두고 쌓인 상금을 받음 (시작하지 않았다면 받을 상금이 없음)
+    pub fn stop_luck_test(&mut self) -> Result<(), GameError> {
+        self.expect(Command::StopLuckTest)?;
+        self.record(ReplayStep::StopLuckTest, |game| {
+            let amount = game.state.luck_test_cache.max(0);
+            let player_mut = &mut game.state.players[game.state.current_turn_idx];
+            player_mut.money += amount;
+            let player_id = player_mut.id;
+            game.emit(GameEvent::LuckTestStopped { player_id, amount });
+            game.state.luck_test_cache = -1;
+            game.now = GameSituation::EndTurn;
+            Ok(())
+        })
+    }
+
+    fn draw_luck_test(&mut self, init_double_lotto: bool) {
+        if self.state.luck_test_cache != 0_i64 {
+            let randvar = self.state.rng.random_bool(1.0/10.0);
             let result = if !randvar {
                 0_i64
             } else if self.state.luck_test_cache < 0 {
@@ -706,15 +964,14 @@ impl GameEngine {
         }
     }
 
-    fn medical_care(&mut self, free: bool) -> bool {
-        let hospital_pos = self.state.board.iter().position(|t| t.tile_type == "Hospital").unwrap();
+    fn medical_care(&mut self, free: bool) -> Result<bool, GameError> {
+        let hospital_pos = Self::find_tile_of_type(&self.state.board, "Hospital")?;
         let hospital_cost = self.state.board[hospital_pos].amount / 2;
 
         let player_index = self.state.current_turn_idx;
+        self.emit(GameEvent::Hospitalized { player_id: self.state.players[player_index].id });
         let player_mut = &mut self.state.players[player_index];
 
-        self.state.log.push("Sent to Hospital!".into());
-
         let tmp = self.state.government_income - hospital_cost;
 
         if !free {
@@ -732,42 +989,121 @@ impl GameEngine {
 
         let crisis = player_mut.money < 0;
         if player_mut.money < 0 {
-            self.prompt_financial_crisis();
+            self.prompt_financial_crisis(Creditor::Government);
         }
-        return !crisis;
+        Ok(!crisis)
     }
 
-    #[wasm_bindgen]
-    pub fn run_turn_script(&mut self, script_action: &str, dices: DicePair, script_cycle: &str) -> Result<(), String> {
-        self.try_run_turn_script(script_action,Some(dices),script_cycle,0)
+    /// `roll_dice`로 굴린 주사위로 턴을 진행
+    ///
+    /// 주사위 출처가 시드 기반이 아니라면 엔진이 굴리지 않은 호스트의 주사위도 받는다 (범위를 벗어난 주사위는 거부).
+    pub fn run_turn_script(&mut self, script_action: &str, dices: DicePair, script_cycle: &str) -> Result<(), GameError> {
+        self.expect(Command::RunTurnScript)?;
+        let step = ReplayStep::RunTurnScript { script_action: self.intern_script(script_action), dices, script_cycle: self.intern_script(script_cycle) };
+        // 주사위는 기록되는 명령 안에서 써야 명령이 실패했을 때 굴린 주사위가 되살아남
+        self.record(step, |game| {
+            let dices = game.take_rolled_dice(dices)?;
+            game.try_run_turn_script(script_action,Some(dices),script_cycle,0)
+        })
     }
 
-    fn prompt_financial_crisis(&mut self) {
-        self.now = GameSituation::PendingFinancialCrisisResponse;
+    /// 엔진의 주사위 출처로 주사위를 굴림
+    ///
+    /// 굴린 주사위는 `run_turn_script`나 `try_to_jailbreak_by_dices`에 쓰기 전까지 다시 굴릴 수 없다.
+    pub fn roll_dice(&mut self) -> Result<DicePair, GameError> {
+        self.expect(Command::RollDice)?;
+        let seeded = self.dice_source.is_seeded();
+        let dices = self.dice_source.roll(&mut self.state.rng)?;
+        dices.validate()?;
+        self.state.rolled_dice = Some(dices);
+        self.now = if self.now == GameSituation::PendingTryToJailbreakResponse {
+            GameSituation::PendingJailbreakByDicesResponse
+        } else {
+            GameSituation::PendingMoveResponse
+        };
+        // 주사위를 굴린 명령은 되돌릴 수 없으므로 직전 상태를 남기지 않음
+        self.push_step(ReplayStep::RollDice { dices, seeded }, None);
+        Ok(dices)
     }
 
-    fn educate(player_mut: &mut Player) {
-        player_mut.education_status.educate();
+    /// 이번 이동이나 탈옥에 쓸 주사위
+    ///
+    /// 엔진이 굴린 주사위가 있다면 그 주사위여야 하고, 없다면 주사위 출처가 호스트의 주사위를 받을 때만 `dices`를 쓴다.
+    fn take_rolled_dice(&mut self, dices: DicePair) -> Result<DicePair, GameError> {
+        dices.validate()?;
+        match self.state.rolled_dice {
+            Some(rolled) if rolled == dices => {
+                self.state.rolled_dice = None;
+                Ok(rolled)
+            },
+            None if self.dice_source.accepts_host_dice() => Ok(dices),
+            _ => Err(GameError::UnrolledDice { first: dices.0, second: dices.1 }),
+        }
     }
 
-    #[wasm_bindgen]
-    pub fn buy(&mut self, pos: u32) {
-        let player_index = self.state.current_turn_idx;
-        let player_mut = &mut self.state.players[player_index];
-        let name = self.state.board[pos as usize].name.clone();
-        player_mut.money -= self.building_cost;
+    /// 엔진이 직접 주사위를 굴려 턴을 진행하고, 굴린 주사위를 반환 (이미 굴렸다면 그 주사위로 진행)
+    pub fn roll_and_run_turn_script(&mut self, script_action: &str, script_cycle: &str) -> Result<DicePair, GameError> {
+        let dices = self.roll_dice_unless_rolled(GameSituation::PendingMoveResponse)?;
+        self.run_turn_script(script_action, dices, script_cycle)?;
+        Ok(dices)
+    }
 
-        self.state.log.push(format!("Player {} bought '{}'!", player_mut.id, name));
-        if let Some((_, v)) = self.state.properties.get_mut(&name) {
-            let tmp = min(3u32, *v + 1);
-            *v = tmp;
-        } else {
-            self.state.properties.insert(name, (player_mut.id, 1u32));
+    /// `rolled` 상황이라면 이미 굴린 주사위, 아니면 새로 굴린 주사위
+    fn roll_dice_unless_rolled(&mut self, rolled: GameSituation) -> Result<DicePair, GameError> {
+        match self.state.rolled_dice {
+            Some(dices) if self.now == rolled => Ok(dices),
+            _ => self.roll_dice(),
+        }
+    }
+
+    /// 게임 상태의 시드로 주사위를 굴리도록 설정 (기본값)
+    pub fn use_seeded_dice(&mut self) {
+        self.dice_source = Box::new(SeededDice);
+    }
+
+    /// 주어진 순서대로 주사위가 나오도록 설정하며, 각 값은 `DicePair`의 직렬화 형식을 따름
+    pub fn use_scripted_dice(&mut self, concatenated_pairs: Vec<u32>) -> Result<(), GameError> {
+        let pairs = concatenated_pairs.into_iter().map(|concatenated| {
+            let dices = DicePair((concatenated >> 16) as u16, concatenated as u16);
+            dices.validate().map(|_| dices)
+        }).collect::<Result<Vec<_>, _>>()?;
+        self.dice_source = Box::new(ScriptedDice::new(pairs));
+        Ok(())
+    }
+
+
+    /// 현재 플레이어가 `payment`에 따라 정부, 시장, 다른 플레이어에게 지불
+    fn pay(&mut self, player_index: usize, payment: Payment) {
+        let payer_id = self.state.players[player_index].id;
+
+        self.emit(GameEvent::Message { message: payment.message });
+
+        if let Some(amount) = payment.gov_amount {
+            self.state.government_income += amount;
+            self.state.players[player_index].money -= amount;
+            self.emit(GameEvent::PaidGovernment { player_id: payer_id, amount });
+        }
+
+        if let Some(amount) = payment.market_amount {
+            self.state.players[player_index].money -= amount;
+            self.emit(GameEvent::PaidMarket { player_id: payer_id, amount });
+        }
+
+        if let (Some(amount), Some(pid)) = (payment.player_amount, payment.to_player_id) {
+            let to_player = self.state.players.iter_mut().find(|player| player.id == pid);
+            if let Some(to_player_mut) = to_player {
+                to_player_mut.money += amount;
+                self.state.players[player_index].money -= amount;
+                self.emit(GameEvent::PaidPlayer { player_id: payer_id, to_player_id: pid, amount });
+            }
         }
-        self.now = GameSituation::EndTurn;
     }
 
-    fn trigger_cycle(&mut self, script: &str) -> Result<(), String> {
+    fn educate(player_mut: &mut Player) {
+        player_mut.education_status.educate();
+    }
+
+    fn trigger_cycle(&mut self, script: &str) -> Result<(), GameError> {
         let salary = self.salary;
         let government_income = self.state.government_income;
         let player_mut = &mut self.state.players[self.state.current_turn_idx];
@@ -792,382 +1128,359 @@ impl GameEngine {
         scope.push_constant("is_graduated", if let EducationStatus::Graduated = education_status { true } else { false });
         scope.push_constant("has_bonus", player_mut.tickets_count.bonus > 0);
 
-        let result: Map = self.engine.eval_with_scope(&mut scope, script).map_err(|e| e.to_string())?;
-        let new_government_income = result["new_government_income"].clone().as_int().unwrap();
-        let remaining_salary = result["remaining_salary"].clone().as_int().unwrap();
-        let basic_income = result["basic_income"].clone().as_int().unwrap();
+        let result: Map = self.engine.eval_with_scope(&mut scope, script)?;
+        let CycleResult { new_government_income, remaining_salary, basic_income } = CycleResult::from_map(&result)?;
 
         self.state.government_income = new_government_income;
         player_mut.money += remaining_salary;
-        self.state.players.iter_mut().for_each(|each_player_mut| {
+        self.state.players.iter_mut().filter(|each_player| !each_player.bankrupt).for_each(|each_player_mut| {
             each_player_mut.money += basic_income;
         });
         if self.state.players[self.state.current_turn_idx].tickets_count.bonus > 0 {
             self.state.pending_ticket.bonus -= 1;
         }
+        let player = &self.state.players[self.state.current_turn_idx];
+        self.emit(GameEvent::PassedStart { player_id: player.id, cycles: player.cycles });
+        self.accrue_loans_on_cycle(self.state.current_turn_idx);
         Ok(())
     }
 
-    #[wasm_bindgen]
-    pub fn borrow_money(&mut self, pid: u32, amount: i64) {
-        let found = self.state.players.iter_mut().find(|player| player.id == pid);
-        if let Some(player_mut) = found {
-            if amount > 0 {
-                let loans_acc = if let Some(&(lid, _, _)) = player_mut.remaining_loans.iter().max_by_key(|&&(lid,_,_)| lid) {
-                    lid+1
-                } else {
-                    0u32
-                };
-                
-                player_mut.remaining_loans.push((loans_acc, amount, 4u32));
-                player_mut.money += amount;
-            }
-        }
-    }
-
-    #[wasm_bindgen]
-    pub fn repay_loan(&mut self, pid: u32, lid: u32, amount: i64) {
-        let found = self.state.players.iter_mut().find(|player| player.id == pid);
-        if let Some(player_mut) = found {
-            if amount > 0 {
-                if let Some((_, rem_amount, _)) = player_mut.remaining_loans.iter_mut().find(|(loan_id, rem_amount, _)| *loan_id == lid && *rem_amount > 0) {
-                    *rem_amount -= amount;
-                    player_mut.money -= amount;
-                    player_mut.money -= amount / 10;
-                }
-                player_mut.remaining_loans.retain(|(_, rem_amount, _)| *rem_amount > 0);
-            }
-        }
+    fn find_player_mut(players: &mut [Player], pid: u32) -> Result<&mut Player, GameError> {
+        players.iter_mut().find(|player| player.id == pid).ok_or(GameError::UnknownPlayer(pid))
     }
 
     /// 턴을 종료하고 다음 플레이어로 넘기는 함수
-    #[wasm_bindgen]
-    pub fn end_turn(&mut self) {
-        self.garbage_collect();
-        if let GameSituation::EndTurn = self.now {
-            let position = self.state.players[self.state.current_turn_idx].position as usize;
-            let is_in_jail = self.state.board[position].tile_type == "Jail";
-            if !self.state.dice_double || is_in_jail {
-                self.state.current_turn_idx = (self.state.current_turn_idx + 1) % self.state.players.len();
-                Self::consume_counter(&mut self.state.catastrophe_counter);
-                Self::consume_counter(&mut self.state.pandemic_counter);
+    pub fn end_turn(&mut self) -> Result<(), GameError> {
+        self.expect(Command::EndTurn)?;
+        self.record(ReplayStep::EndTurn, |game| {
+            let player_id = game.state.players[game.state.current_turn_idx].id;
+            let position = game.state.players[game.state.current_turn_idx].position as usize;
+            let is_in_jail = game.state.board[position].tile_type == "Jail";
+            let advancing = !game.state.dice_double || is_in_jail || game.state.players[game.state.current_turn_idx].bankrupt;
+            // 만기가 된 대출을 갚지 못하면 재정 위기를 해결한 뒤 다시 턴을 끝내야 함
+            if !game.settle_loans_on_end_turn(game.state.current_turn_idx, advancing) {
+                return Ok(());
             }
-            self.state.dice_double = false;
-            self.state.log.push(format!("--- End of Turn ---"));
-            self.before_begin_turn();
-        }
+            if advancing {
+                game.state.current_turn_idx = game.next_active_player_idx();
+                Self::consume_counter(&mut game.state.catastrophe_counter);
+                Self::consume_counter(&mut game.state.pandemic_counter);
+            }
+            game.state.dice_double = false;
+            game.state.turn_count += 1;
+            game.emit(GameEvent::TurnEnded { player_id });
+            game.before_begin_turn();
+            Ok(())
+        })
     }
 
     fn before_begin_turn(&mut self) {
-        let before_four_cycles = self.state.players.iter().filter_map(|player| if player.cycles < 4 { Some(player.id) } else { None }).collect::<Vec<_>>();
+        let end_reason = self.check_end_condition();
         let current_turn_idx = self.state.current_turn_idx;
         let player = &self.state.players[current_turn_idx];
+        let player_id = player.id;
         let position = player.position;
-        let tile = &self.state.board[position as usize];
+        let in_jail = self.state.board[position as usize].tile_type == "Jail" && player.remaining_jail_turns > 0;
 
-        if before_four_cycles.is_empty() {
-            self.state.log.push(format!("The game has ended."));
+        if let Some(reason) = end_reason {
+            self.state.end_reason = Some(reason);
+            self.emit(GameEvent::GameEnded);
             self.now = GameSituation::EndGame;
-        } else if tile.tile_type == "Jail" && player.remaining_jail_turns > 0 {
-            self.state.log.push(format!("It is now Player {}'s turn.", player.id));
+        } else if in_jail {
+            self.emit(GameEvent::TurnStarted { player_id });
             self.now = GameSituation::PendingTryToJailbreakResponse;
         } else {
-            self.state.log.push(format!("It is now Player {}'s turn.", player.id));
+            self.emit(GameEvent::TurnStarted { player_id });
             self.now = GameSituation::PendingRollResponse;
         }
     }
 
-    #[wasm_bindgen]
-    pub fn try_to_jailbreak_by_dices(&mut self, dices: DicePair) {
-        let current_turn_idx = self.state.current_turn_idx;
-        let player_mut = &mut self.state.players[current_turn_idx];
-        if dices.is_double() {
-            player_mut.remaining_jail_turns = 0;
-        }
-        self.now = GameSituation::EndTurn;
+    /// `roll_dice`로 굴린 주사위로 탈옥을 시도 (`run_turn_script`처럼 주사위 출처가 시드 기반이 아니라면 호스트가 굴린 주사위도 받음)
+    pub fn try_to_jailbreak_by_dices(&mut self, dices: DicePair) -> Result<(), GameError> {
+        self.expect(Command::TryToJailbreakByDices)?;
+        self.record(ReplayStep::TryToJailbreakByDices { dices }, |game| {
+            let dices = game.take_rolled_dice(dices)?;
+            let current_turn_idx = game.state.current_turn_idx;
+            game.emit(GameEvent::DiceRolled { player_id: game.state.players[current_turn_idx].id, first: dices.0, second: dices.1 });
+            let player_mut = &mut game.state.players[current_turn_idx];
+            if dices.is_double() {
+                player_mut.remaining_jail_turns = 0;
+            }
+            game.now = GameSituation::EndTurn;
+            Ok(())
+        })
     }
 
-    #[wasm_bindgen]
-    pub fn give_up_jailbreak(&mut self) {
-        let current_turn_idx = self.state.current_turn_idx;
-        let player_mut = &mut self.state.players[current_turn_idx];
-        if player_mut.remaining_jail_turns > 0 {
-            player_mut.remaining_jail_turns -= 1;
-        }
-        self.now = GameSituation::EndTurn;
+    /// 엔진이 직접 주사위를 굴려 탈옥을 시도하고, 굴린 주사위를 반환 (이미 굴렸다면 그 주사위로 시도)
+    pub fn roll_and_try_to_jailbreak(&mut self) -> Result<DicePair, GameError> {
+        let dices = self.roll_dice_unless_rolled(GameSituation::PendingJailbreakByDicesResponse)?;
+        self.try_to_jailbreak_by_dices(dices)?;
+        Ok(dices)
     }
 
-    #[wasm_bindgen]
-    pub fn try_to_jailbreak_by_money(&mut self) {
-        let current_turn_idx = self.state.current_turn_idx;
-        let player_mut = &mut self.state.players[current_turn_idx];
-        let amount = self.state.board.iter().find_map(|tile| if tile.tile_type == "Jail" { Some(tile.amount) } else { None }).unwrap();
-        if player_mut.money >= amount {
+    pub fn give_up_jailbreak(&mut self) -> Result<(), GameError> {
+        self.expect(Command::GiveUpJailbreak)?;
+        self.record(ReplayStep::GiveUpJailbreak, |game| {
+            let current_turn_idx = game.state.current_turn_idx;
+            let player_mut = &mut game.state.players[current_turn_idx];
+            if player_mut.remaining_jail_turns > 0 {
+                player_mut.remaining_jail_turns -= 1;
+            }
+            game.now = GameSituation::EndTurn;
+            Ok(())
+        })
+    }
+
+    pub fn try_to_jailbreak_by_money(&mut self) -> Result<(), GameError> {
+        self.expect(Command::TryToJailbreakByMoney)?;
+        self.record(ReplayStep::TryToJailbreakByMoney, |game| {
+            let current_turn_idx = game.state.current_turn_idx;
+            let amount = game.state.board[Self::find_tile_of_type(&game.state.board, "Jail")?].amount;
+            let player_mut = &mut game.state.players[current_turn_idx];
+            if player_mut.money < amount {
+                return Err(GameError::InsufficientFunds { required: amount, available: player_mut.money });
+            }
             player_mut.remaining_jail_turns = 0;
             player_mut.money -= amount;
-            self.now = GameSituation::EndTurn;
-        }
+            game.now = GameSituation::EndTurn;
+            Ok(())
+        })
     }
 
-    #[wasm_bindgen]
-    pub fn get_random_chance_card(&mut self) {
-        let rand_card_entry_idx = rand::random::<u32>() as usize % self.state.chance_cards_inventory.len();
-        let card_id = self.state.chance_cards_inventory.keys().collect::<Vec<_>>()[rand_card_entry_idx].clone();
-        
-        self.pending_chance_card_id = Some(card_id);
-        self.now = GameSituation::PendingCheckChanceCardResponse;
+    pub fn get_random_chance_card(&mut self) -> Result<(), GameError> {
+        self.expect(Command::GetRandomChanceCard)?;
+        self.record(ReplayStep::GetRandomChanceCard, |game| {
+            if game.state.chance_cards_inventory.is_empty() {
+                return Err(GameError::EmptyChanceDeck);
+            }
+            // HashMap의 순회 순서는 실행마다 다르므로, 시드가 같으면 같은 카드가 나오도록 정렬 후 선택
+            let mut card_ids = game.state.chance_cards_inventory.keys().collect::<Vec<_>>();
+            card_ids.sort();
+            let card_id = card_ids[game.state.rng.random_range(0..card_ids.len())].clone();
+
+            let player_id = game.state.players[game.state.current_turn_idx].id;
+            game.emit(GameEvent::CardDrawn { player_id, card_id: card_id.clone() });
+            game.pending_chance_card_id = Some(card_id);
+            game.now = GameSituation::PendingCheckChanceCardResponse;
+            Ok(())
+        })
     }
 
-    fn property_swap(&mut self, to_give: &String, to_get: &String) {
-        let pair = (self.state.properties[to_give].0.clone(), self.state.properties[to_get].0.clone());
-        self.state.properties.iter_mut().for_each(|(name, (owner_id,_ ))| {
+    fn property_swap(&mut self, to_give: &String, to_get: &String) -> Result<(), GameError> {
+        let owner_of = |name: &String| self.state.properties.get(name).map(|ownership| ownership.owner_id).ok_or_else(|| GameError::UnownedProperty(name.clone()));
+        let pair = (owner_of(to_give)?, owner_of(to_get)?);
+        self.state.properties.iter_mut().for_each(|(name, ownership)| {
             if *name == *to_give {
-                *owner_id = pair.1;
+                ownership.owner_id = pair.1;
             } else if *name == *to_get {
-                *owner_id = pair.0;
+                ownership.owner_id = pair.0;
             }
         });
+        Ok(())
     }
 
-    #[wasm_bindgen]
-    pub fn check_chance_card(&mut self, script_chance_action: &str, script_cycle: &str, payload_json: Option<String>) -> Result<(), String> {
-        if let Some(cid) = &self.pending_chance_card_id {
-            
-            let current_turn_idx = self.state.current_turn_idx;
-            let player_mut = &mut self.state.players[current_turn_idx];
-            let player_money = player_mut.money.clone();
+    /// 찬스 카드 스크립트를 실행해 카드가 일으킬 행동을 구함 (상태는 바꾸지 않음)
+    pub(crate) fn eval_chance_card(&self, cid: &str, script_chance_action: &str, payload_json: Option<&str>) -> Result<ChanceAction, GameError> {
+        let player = &self.state.players[self.state.current_turn_idx];
+        let player_money = player.money;
 
-            let mut scope = Scope::new();
-            scope.push("card_id", cid.clone());
-            let payload = if let Some(s) = payload_json {
-                let json_str = s.as_str();
-                self.engine.parse_json(r#json_str, true).map_err(|e| e.to_string())?
-            } else {
-                self.engine.parse_json(r#"{}"#, true).map_err(|e| e.to_string())?
-            };
-            scope.push("payload", payload);
+        let mut scope = Scope::new();
+        scope.push("card_id", cid.to_string());
+        let payload = if let Some(json_str) = payload_json {
+            self.engine.parse_json(r#json_str, true).map_err(|e| GameError::InvalidJson(e.to_string()))?
+        } else {
+            self.engine.parse_json(r#"{}"#, true)?
+        };
+        scope.push("payload", payload);
 
-            let (my_properties, others_properties) = Self::get_owned_properties(&self.state.properties,player_mut.id);
-            let my_houses_countsum = my_properties.iter().filter_map(|(name, count)| {
-                let tile_type = self.state.board.iter().find_map(|tile| {
-                    if tile.name == *name {
-                        Some(tile.tile_type.clone())
-                    } else {
-                        None
-                    }
-                });
-                if let Some(tt) = tile_type && tt == "Property" {
-                    Some(*count as i64)
+        let (my_properties, others_properties) = Self::get_owned_properties(&self.state.properties, player.id);
+        let my_houses_countsum = my_properties.iter().filter_map(|(name, count)| {
+            let tile_type = self.state.board.iter().find_map(|tile| {
+                if tile.name == *name {
+                    Some(tile.tile_type.clone())
                 } else {
                     None
                 }
-            }).sum::<i64>();
-            scope.push("my_properties", my_properties);
-            scope.push("my_houses_countsum", my_houses_countsum);
-            scope.push("others_properties", others_properties);
-            scope.push("player_money", player_money);
-
-            let result: Map = self.engine.eval_with_scope(&mut scope, script_chance_action).map_err(|e| e.to_string())?;
-            let action_type = result["type"].clone().into_string().unwrap();
-
-            match action_type.as_str() {
-                "Earn" => {
-                    let amount = result["amount"].clone().as_int().unwrap();
+            });
+            if let Some(tt) = tile_type && tt == "Property" {
+                Some(*count as i64)
+            } else {
+                None
+            }
+        }).sum::<i64>();
+        scope.push("my_properties", my_properties);
+        scope.push("my_houses_countsum", my_houses_countsum);
+        scope.push("others_properties", others_properties);
+        scope.push("player_money", player_money);
+
+        let result: Map = self.engine.eval_with_scope(&mut scope, script_chance_action)?;
+
+        ChanceAction::from_map(&result)
+    }
+
+    pub fn check_chance_card(&mut self, script_chance_action: &str, script_cycle: &str, payload_json: Option<String>) -> Result<(), GameError> {
+        self.expect(Command::CheckChanceCard)?;
+        let step = ReplayStep::CheckChanceCard { script_chance_action: self.intern_script(script_chance_action), script_cycle: self.intern_script(script_cycle), payload_json: payload_json.clone() };
+        self.record(step, |game| game.apply_chance_card(script_chance_action, script_cycle, payload_json))
+    }
+
+    /// 뽑은 찬스 카드의 스크립트를 실행하고 그 행동을 적용
+    fn apply_chance_card(&mut self, script_chance_action: &str, script_cycle: &str, payload_json: Option<String>) -> Result<(), GameError> {
+        if let Some(cid) = self.pending_chance_card_id.clone() {
+            let action = self.eval_chance_card(&cid, script_chance_action, payload_json.as_deref())?;
+            let current_turn_idx = self.state.current_turn_idx;
+            let player_mut = &mut self.state.players[current_turn_idx];
+            let player_id = player_mut.id;
+
+            match action {
+                ChanceAction::Earn { amount } => {
                     player_mut.money += amount;
                     self.now = GameSituation::EndTurn;
                 },
-                "Earthquake" => {
-                    let tmp = self.state.properties.iter().filter_map(|(name, (owner_id, owned_amount))| {
-                        if *owner_id == player_mut.id {
-                            if *owned_amount > 1 {
-                                Some((name.clone(), (*owner_id, (*owned_amount) - 1)))
-                            } else {
-                                None
-                            }
+                ChanceAction::Earthquake => {
+                    let tmp = self.state.properties.iter().map(|(name, ownership)| {
+                        if ownership.owner_id == player_id && self.is_top_building_destructible(name, ownership.buildings) {
+                            // 건물이 모두 부서져도 땅은 남음
+                            (name.clone(), Ownership { buildings: ownership.buildings - 1, ..*ownership })
                         } else {
-                            Some((name.clone(), (*owner_id, *owned_amount)))
+                            (name.clone(), *ownership)
                         }
                     }).collect::<HashMap<_, _>>();
                     self.state.properties = tmp;
                     self.now = GameSituation::EndTurn;
                 },
-                "GoToJail" => {
-                    let jail_pos = self.state.board.iter().position(|t| t.tile_type == "Jail").unwrap();
+                ChanceAction::GoToJail => {
+                    let jail_pos = Self::find_tile_of_type(&self.state.board, "Jail")?;
                     player_mut.position = jail_pos as u32;
-                    self.state.log.push("Sent to Jail!".into());
+                    self.emit(GameEvent::Jailed { player_id });
                     self.now = GameSituation::EndTurn;
                 },
-                "GoToHospital" => {
-                    let hospital_pos = self.state.board.iter().position(|t| t.tile_type == "Hospital").unwrap();
+                ChanceAction::GoToHospital => {
+                    let hospital_pos = Self::find_tile_of_type(&self.state.board, "Hospital")?;
 
                     player_mut.position = hospital_pos as u32;
                     if self.state.players[self.state.current_turn_idx].tickets_count.free_hospital > 0 {
                         self.now = GameSituation::PendingUseTicketResponse;
                     } else {
-                        let crisis = self.medical_care(false);
-                        if !crisis {
-                            self.now = GameSituation::EndGame;
+                        let settled = self.medical_care(false)?;
+                        if settled {
+                            self.now = GameSituation::EndTurn;
                         }
                     }
                 },
-                "GoToUniversity" => {
-                    let univ_pos = self.state.board.iter().position(|t| t.tile_type == "University").unwrap();
+                ChanceAction::GoToUniversity => {
+                    let univ_pos = Self::find_tile_of_type(&self.state.board, "University")?;
                     player_mut.position = univ_pos as u32;
-                    self.state.log.push("Sent to University!".into());
                     Self::educate(player_mut);
+                    self.emit(GameEvent::SentToUniversity { player_id });
                     self.now = GameSituation::EndTurn;
                 },
-                "GetTicket" => {
-                    let kind = result["kind"].clone().into_string().unwrap();
+                ChanceAction::GetTicket { kind } => {
                     self.state.players[self.state.current_turn_idx].tickets_count += TicketCount::get_one_ticket(kind.as_str());
                     self.now = GameSituation::EndTurn;
                 },
-                "TwistOfFate" => {
-                    let dice_a = result["dice_a"].clone().as_int().unwrap() as usize;
-                    let dice_b = result["dice_b"].clone().as_int().unwrap() as usize;
-                    let players_count = self.state.players.len();
+                ChanceAction::TwistOfFate { dice_a, dice_b } => {
+                    // 파산한 플레이어와는 부동산을 맞바꾸지 않음
                     let current_turn_idx = self.state.current_turn_idx;
-                    let target_turn_idx = (current_turn_idx + dice_a + dice_b) % players_count;
+                    let target_turn_idx = self.active_player_idx_after(dice_a + dice_b);
                     let swap_result = self.swap_all_properties(current_turn_idx, target_turn_idx);
                     if swap_result {
                         self.now = GameSituation::EndTurn;
                     }
                 },
-                "PayTo" => {
+                ChanceAction::PayTo(payment) => {
                     let player_index = self.state.current_turn_idx;
-                    let government_amount = if let Ok(amount) = result["gov_amount"].clone().as_int() {
-                        Some(amount)
-                    } else { None };
-                    let market_amount = if let Ok(amount) = result["market_amount"].clone().as_int() {
-                        Some(amount)
-                    } else { None };
-                    let to_player = if let Ok(amount) = result["player_amount"].clone().as_int() && let Ok(pid) = result["to_player_id"].clone().as_int() {
-                        Some((amount, pid as u32))
-                    } else { None };
-                    let payer_id = self.state.players[player_index].id;
-
-                    let message = result["message"].clone().into_string().unwrap();
-                    self.state.log.push(message);
-
-                    if let Some(amount) = government_amount {
-                        self.state.government_income += amount;
-                        self.state.players[player_index].money -= amount;
-                        self.state.log.push(format!("\tPlayer {} Paid ${} to the government.", payer_id, amount));
-                    }
-
-                    if let Some(amount) = market_amount {
-                        self.state.players[player_index].money -= amount;
-                        self.state.log.push(format!("\tPlayer {} Paid ${} to the market.", payer_id, amount));
-                    }
-
-                    if let Some((amount, pid)) = to_player {
-                        let to_player = self.state.players.iter_mut().find(|player| player.id == pid);
-                        if let Some(to_player_mut) = to_player {
-                            to_player_mut.money += amount;
-                            self.state.players[player_index].money -= amount;
-                            self.state.log.push(format!("\tPlayer {} Paid ${} to Player {}.", payer_id, amount, pid));
-                        }
-                    }
+                    let creditor = payment.creditor();
+                    self.pay(player_index, payment);
 
                     if self.state.players[player_index].money < 0 {
-                        self.prompt_financial_crisis();
+                        self.prompt_financial_crisis(creditor);
                     } else {
                         self.now = GameSituation::EndTurn;
                     }
                 },
-                "WarpToPosition" => {
-                    let dest = result["position"].clone().as_int().unwrap() as u32;
+                ChanceAction::WarpToPosition { position: dest } => {
+                    Self::check_position(&self.state.board, dest)?;
                     player_mut.position = dest;
-                    self.state.log.push(format!("Warped to {}!", self.state.board[dest as usize].name));
+                    self.emit(GameEvent::Warped { player_id, position: dest, tile_name: self.state.board[dest as usize].name.clone() });
                     self.now = GameSituation::EndTurn;
                 },
-                "TravelToPosition" => {
-                    let dest = result["position"].clone().as_int().unwrap() as u32;
+                ChanceAction::TravelToPosition { position: dest } => {
+                    Self::check_position(&self.state.board, dest)?;
                     let old_pos = player_mut.position.clone();
                     player_mut.position = dest;
-                    self.state.log.push(format!("Traveled to {}!", self.state.board[dest as usize].name));
+                    self.emit(GameEvent::Traveled { player_id, position: dest, tile_name: self.state.board[dest as usize].name.clone() });
                     if old_pos >= dest {
                         self.trigger_cycle(script_cycle)?;
                     }
                     self.now = GameSituation::EndTurn;
                 },
-                "DestructOnePerEach" => {
-                    let raw_targets = result["targets"].clone().into_array().unwrap();
-                    let processed_targets = raw_targets.iter().filter_map(|item| {
-                        if item.type_name() == "string" {
-                            Some(item.clone().into_string().unwrap())
-                        } else {
-                            None
-                        }
-                    }).collect::<Vec<_>>();
-                    let targets = processed_targets.iter().map(|s| s.as_str()).collect::<Vec<_>>();
-                    self.state.properties.iter_mut().for_each(|(name, (_, owned_amount))| {
-                        let name_as_str = name.as_str();
-                        if targets.contains(&name_as_str) {
-                            if *owned_amount > 0 {
-                                *owned_amount -= 1;
-                            }
+                ChanceAction::DestructOnePerEach { targets: raw_targets } => {
+                    // 부서지지 않는 건물이 맨 위에 있는 부동산은 제외
+                    let targets = raw_targets.iter().filter_map(|item| item.as_str()).filter(|&name| {
+                        self.state.properties.get(name).is_some_and(|ownership| self.is_top_building_destructible(name, ownership.buildings))
+                    }).map(String::from).collect::<Vec<_>>();
+                    self.state.properties.iter_mut().for_each(|(name, ownership)| {
+                        if targets.contains(name) {
+                            ownership.buildings -= 1;
                         }
                     });
                     self.now = GameSituation::EndTurn;
                 },
-                "Pandemic" => {
+                ChanceAction::Pandemic => {
                     self.state.pandemic_counter += self.state.players.len() + 1;
                     self.now = GameSituation::EndTurn;
                 },
-                "FreeConstruction" => {
-                    let target = result["target"].clone().into_string().unwrap();
-                    let max_buildings = if let Some(&max_buildings) = self.state.consts.get("MAX_BUILDINGS") && max_buildings > 0 {
-                        max_buildings
-                    } else {
-                        1
-                    };
-                    if let Some((owner_id, owned_amount)) = self.state.properties.get_mut(&target) && *owner_id == player_mut.id && *owned_amount < max_buildings {
-                        *owned_amount += 1;
+                ChanceAction::FreeConstruction { target } => {
+                    let can_build = self.next_building_tier(&target).is_some();
+                    if can_build && let Some(ownership) = self.state.properties.get_mut(&target) && ownership.owner_id == player_id {
+                        ownership.buildings += 1;
                         self.now = GameSituation::EndTurn;
                     }
                 },
-                "Catastrophe" => {
+                ChanceAction::Catastrophe => {
                     self.state.catastrophe_counter += self.state.players.len() + 1;
                     self.now = GameSituation::EndTurn;
                 },
-                "NOP" => {
+                ChanceAction::Nop => {
                     self.now = GameSituation::EndTurn;
                 },
-                "GoToPayElectricityFee" => {
-                    let using_ticket = result["using_ticket"].clone().as_bool().unwrap();
-                    let (elec_pos, elec_tile) = self.state.board.iter().enumerate().find(|&(_, tile)| tile.name.as_str() == "Electricity").unwrap();
+                ChanceAction::GoToPayElectricityFee { using_ticket } => {
+                    let (elec_pos, elec_tile) = self.state.board.iter().enumerate().find(|&(_, tile)| tile.name.as_str() == "Electricity").ok_or_else(|| GameError::TileNotFound("Electricity".into()))?;
                     player_mut.position = elec_pos as u32;
-                    self.state.log.push("Sent to Electricity!".into());
-                    
+
                     if using_ticket && player_mut.tickets_count.no_tax > 0 {
                         player_mut.tickets_count.no_tax -= 1;
                     } else {
                         player_mut.money -= elec_tile.amount;
                     }
+                    let crisis = player_mut.money < 0;
+                    self.emit(GameEvent::SentToElectricity { player_id });
 
-                    if player_mut.money < 0 {
-                        self.prompt_financial_crisis();
+                    if crisis {
+                        self.prompt_financial_crisis(Creditor::Government);
                     } else {
                         self.now = GameSituation::EndTurn;
                     }
                 },
-                "GraduateNow" => {
+                ChanceAction::GraduateNow => {
                     player_mut.education_status = EducationStatus::Graduated;
                     self.now = GameSituation::EndTurn;
                 },
-                "PropertySwap" => {
-                    let to_get = result["to_get"].clone().into_string().unwrap();
-                    let to_give = result["to_give"].clone().into_string().unwrap();
-                    self.property_swap(&to_give, &to_get);
+                ChanceAction::PropertySwap { to_get, to_give } => {
+                    self.property_swap(&to_give, &to_get)?;
                     self.now = GameSituation::EndTurn;
                 }
                 // ...
-                _ => {
+                ChanceAction::Other => {
                     return Ok(());
                 }
             }
         }
+        // 카드가 처리되어 다른 상황으로 넘어갔다면 더 이상 대기 중인 카드가 아님
+        if self.now != GameSituation::PendingCheckChanceCardResponse {
+            self.pending_chance_card_id = None;
+        }
         Ok(())
     }
 
@@ -1178,42 +1491,98 @@ impl GameEngine {
         }
     }
 
-    #[inline(always)]
-    fn garbage_collect(&mut self) {
-        _ = self.state.properties.extract_if(|_, (_, owned_amount)| {
-            *owned_amount == 0u32
-        });
-    }
-
     fn swap_all_properties(&mut self, a_turn_idx: usize, b_turn_idx: usize) -> bool {
         if a_turn_idx == b_turn_idx {
             false
         } else {
             let (a_id, b_id) = (self.state.players[a_turn_idx].id, self.state.players[b_turn_idx].id);
             let cloned = self.state.properties.clone();
-            let of_a = cloned.iter().filter_map(|(name, (owner_id, owned_amount))| {
-                if *owner_id == a_id && *owned_amount > 0 {
+            let of_a = cloned.iter().filter_map(|(name, ownership)| {
+                if ownership.owner_id == a_id {
                     Some(name.as_str())
                 } else { None }
             }).collect::<Vec<_>>();
-            let of_b = cloned.iter().filter_map(|(name, (owner_id, owned_amount))| {
-                if *owner_id == b_id && *owned_amount > 0 {
+            let of_b = cloned.iter().filter_map(|(name, ownership)| {
+                if ownership.owner_id == b_id {
                     Some(name.as_str())
                 } else { None }
             }).collect::<Vec<_>>();
-            self.state.properties.iter_mut().for_each(|(name, (owner_id, _))| {
+            self.state.properties.iter_mut().for_each(|(name, ownership)| {
                 if of_a.contains(&name.as_str()) {
-                    *owner_id = b_id;
+                    ownership.owner_id = b_id;
                 } else if of_b.contains(&name.as_str()) {
-                    *owner_id = a_id;
+                    ownership.owner_id = a_id;
                 }
             });
             true
         }
     }
 
-    #[wasm_bindgen]
+    /// 현재 엔진이 기다리고 있는 상황
+    pub fn get_situation(&self) -> GameSituation {
+        self.now
+    }
+
+    /// 현재 상황에서 호출할 수 있는 명령(메서드 이름)의 목록
+    pub fn get_legal_actions(&self) -> Vec<String> {
+        self.legal_commands().into_iter().map(|command| command.name().to_string()).collect()
+    }
+
+
+    /// `consts`에 `key`가 있으면 그 값, 없으면 `default`
+    fn const_or(&self, key: &str, default: u32) -> u32 {
+        self.state.consts.get(key).copied().unwrap_or(default)
+    }
+
+    /// 현재 상황에서 `command`가 허용되지 않으면 `GameError::InvalidSituation`
+    fn expect(&self, command: Command) -> Result<(), GameError> {
+        if self.legal_commands().contains(&command) {
+            Ok(())
+        } else {
+            Err(GameError::InvalidSituation { command, situation: self.now })
+        }
+    }
+
+
+
+    pub fn get_seed(&self) -> u64 {
+        self.state.rng.seed()
+    }
+
+    pub fn get_ruleset_hash(&self) -> String {
+        save::ruleset_hash(&serde_json::to_value(self.snapshot()).unwrap())
+    }
+
+    fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            state: self.state.clone(),
+            salary: self.salary,
+            building_cost: self.building_cost,
+            now: self.now,
+            pending_chance_card_id: self.pending_chance_card_id.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 호스트의 콜백으로 주사위를 굴리도록 설정
+    pub fn use_host_dice(&mut self, callback: js_sys::Function) {
+        self.dice_source = Box::new(HostDice::new(callback));
+    }
+
+    /// 현재 상황과 그 결정에 필요한 맥락 (`PendingDecision`의 JSON)
+    pub fn get_pending_decision(&self) -> String {
+        serde_json::to_string(&self.pending_decision()).unwrap()
+    }
+
     pub fn get_state_as_json(&self) -> String {
         serde_json::to_string(&self.state).unwrap()
     }
-}
\ No newline at end of file
+
+    /// 스키마 버전, 엔진 버전, 룰셋 해시를 포함한 세이브 파일 형식으로 직렬화
+    pub fn get_snapshot_as_json(&self) -> String {
+        serde_json::to_string(&self.save_file()).unwrap()
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, Creditor, GameEngine, GameError, GameEvent, Loan, Player, ReplayStep};
+
+impl GameEngine {
+    fn loans_in_cycles(&self) -> bool {
+        self.const_or("LOAN_TERM_IN_CYCLES", 0) > 0
+    }
+
+    /// 한 기간이 지남: 이자를 붙이고 남은 기간을 줄임
+    fn accrue_loans(&mut self, player_index: usize) {
+        let interest_percent = self.const_or("LOAN_INTEREST_PERCENT", 5) as i64;
+        let player_mut = &mut self.state.players[player_index];
+        for loan in player_mut.remaining_loans.iter_mut() {
+            loan.amount += loan.amount * interest_percent / 100;
+            loan.remaining_turns = loan.remaining_turns.saturating_sub(1);
+        }
+    }
+
+    /// 바퀴를 채웠을 때 (`LOAN_TERM_IN_CYCLES`일 때만) 대출의 기간을 보냄
+    pub(crate) fn accrue_loans_on_cycle(&mut self, player_index: usize) {
+        if self.loans_in_cycles() {
+            self.accrue_loans(player_index);
+        }
+    }
+
+    /// 턴이 끝날 때 만기가 된 대출을 강제로 상환하고, 턴 단위이며 다음 플레이어로 넘어간다면(`advancing`) 기간을 보냄
+    ///
+    /// 현금이 모자라 재정 위기에 빠졌다면 `false`
+    pub(crate) fn settle_loans_on_end_turn(&mut self, player_index: usize, advancing: bool) -> bool {
+        let player_mut = &mut self.state.players[player_index];
+        let player_id = player_mut.id;
+        let (due, remaining): (Vec<_>, Vec<_>) = player_mut.remaining_loans.drain(..).partition(|loan| loan.remaining_turns == 0);
+        player_mut.remaining_loans = remaining;
+        player_mut.money -= due.iter().map(|loan| loan.amount).sum::<i64>();
+        for loan in due {
+            self.emit(GameEvent::LoanCollected { player_id, loan_id: loan.id, amount: loan.amount });
+        }
+        if self.state.players[player_index].money < 0 {
+            self.emit(GameEvent::LoanDefaulted { player_id });
+            self.prompt_financial_crisis(Creditor::Market);
+            return false;
+        }
+        if advancing && !self.loans_in_cycles() {
+            self.accrue_loans(player_index);
+        }
+        true
+    }
+
+    /// 더 빌릴 수 있는 금액 (`LOAN_LIMIT_PERCENT`가 0이면 `None`)
+    pub fn loan_limit(&self, player: &Player) -> Option<i64> {
+        let limit_percent = self.const_or("LOAN_LIMIT_PERCENT", 0) as i64;
+        if limit_percent == 0 {
+            return None;
+        }
+        let loans = player.remaining_loans.iter().map(|loan| loan.amount).sum::<i64>();
+        let assets = self.net_worth(player) + loans;
+        Some((assets * limit_percent / 100 - loans).max(0))
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    pub fn borrow_money(&mut self, pid: u32, amount: i64) -> Result<(), GameError> {
+        self.expect(Command::BorrowMoney)?;
+        self.record(ReplayStep::BorrowMoney { player_id: pid, amount }, |game| {
+            if amount <= 0 {
+                return Err(GameError::InvalidAmount(amount));
+            }
+            let player = game.state.players.iter().find(|player| player.id == pid).ok_or(GameError::UnknownPlayer(pid))?;
+            if player.bankrupt {
+                return Err(GameError::PlayerBankrupt(pid));
+            }
+            if let Some(limit) = game.loan_limit(player) && amount > limit {
+                return Err(GameError::LoanLimitExceeded { requested: amount, limit });
+            }
+            let term = game.const_or("LOAN_TERM", 4);
+            let player_mut = Self::find_player_mut(&mut game.state.players, pid)?;
+            let loans_acc = if let Some(loan) = player_mut.remaining_loans.iter().max_by_key(|loan| loan.id) {
+                loan.id+1
+            } else {
+                0u32
+            };
+
+            player_mut.remaining_loans.push(Loan { id: loans_acc, amount, remaining_turns: term });
+            player_mut.money += amount;
+            game.emit(GameEvent::LoanTaken { player_id: pid, loan_id: loans_acc, amount });
+            game.settle_financial_crisis();
+            Ok(())
+        })
+    }
+
+    /// 대출금의 일부 또는 전부를 갚음 (남은 대출금보다 많이 갚지는 않음)
+    pub fn repay_loan(&mut self, pid: u32, lid: u32, amount: i64) -> Result<(), GameError> {
+        self.expect(Command::RepayLoan)?;
+        self.record(ReplayStep::RepayLoan { player_id: pid, loan_id: lid, amount }, |game| {
+            let player_mut = Self::find_player_mut(&mut game.state.players, pid)?;
+            if amount <= 0 {
+                return Err(GameError::InvalidAmount(amount));
+            }
+            let money = player_mut.money;
+            let loan = player_mut.remaining_loans.iter_mut().find(|loan| loan.id == lid && loan.amount > 0).ok_or(GameError::UnknownLoan { player_id: pid, loan_id: lid })?;
+            let amount = amount.min(loan.amount);
+            if money < amount {
+                return Err(GameError::InsufficientFunds { required: amount, available: money });
+            }
+            loan.amount -= amount;
+            player_mut.money -= amount;
+            player_mut.remaining_loans.retain(|loan| loan.amount > 0);
+            game.emit(GameEvent::LoanRepaid { player_id: pid, loan_id: lid, amount });
+            Ok(())
+        })
+    }
+
+    /// 더 빌릴 수 있는 금액 (제한이 없으면 `undefined`)
+    pub fn get_loan_limit(&self, pid: u32) -> Result<Option<i64>, GameError> {
+        let player = self.state.players.iter().find(|player| player.id == pid).ok_or(GameError::UnknownPlayer(pid))?;
+        Ok(self.loan_limit(player))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, GameError, GameSituation};
+
+    #[test]
+    fn loan_period_passes_only_when_the_turn_moves_on() {
+        let mut game = testing::game(&[("LOAN_TERM", 4), ("LOAN_INTEREST_PERCENT", 10)]);
+        game.borrow_money(1, 100_000).unwrap();
+
+        // 더블로 얻은 턴
+        game.state.dice_double = true;
+        game.now = GameSituation::EndTurn;
+        game.end_turn().unwrap();
+        assert_eq!(game.state.current_turn_idx, 0);
+        let loan = &game.state.players[0].remaining_loans[0];
+        assert_eq!((loan.amount, loan.remaining_turns), (100_000, 4));
+
+        game.now = GameSituation::EndTurn;
+        game.end_turn().unwrap();
+        assert_eq!(game.state.current_turn_idx, 1);
+        let loan = &game.state.players[0].remaining_loans[0];
+        assert_eq!((loan.amount, loan.remaining_turns), (110_000, 3));
+    }
+
+    #[test]
+    fn bankrupt_player_cannot_borrow() {
+        let mut game = testing::game(&[]);
+        game.state.players[1].bankrupt = true;
+        game.state.players[1].money = 0;
+        assert!(matches!(game.borrow_money(2, 100_000), Err(GameError::PlayerBankrupt(2))));
+        assert_eq!(game.state.players[1].money, 0);
+        assert!(game.state.players[1].remaining_loans.is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use serde_json::{Map, Value};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameEngine, GameError, GameEvent, ReplayStep};
+
+/// 내장 메시지 카탈로그 (로케일, JSON)
+const BUILTIN_CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("messages/en.json")),
+    ("ko", include_str!("messages/ko.json")),
+];
+
+static ENGLISH: LazyLock<MessageCatalog> = LazyLock::new(|| MessageCatalog::builtin("en").unwrap());
+
+/// 사건을 로그 문장으로 옮기는 로케일별 문장 틀
+///
+/// 틀의 키는 사건의 `type`이며, 같은 사건이라도 문장이 달라지는 경우에는
+/// `Landed.Unowned`처럼 `.`으로 구분한 이름을 붙인다 (`GameEvent::message_key`).
+/// 틀 안의 `{필드}`는 사건의 같은 이름의 필드 값으로 채워진다.
+/// 카탈로그에 없는 틀은 영어 틀을 쓴다.
+#[derive(Clone, Debug)]
+pub struct MessageCatalog {
+    locale: String,
+    templates: HashMap<String, String>,
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        Self::english().clone()
+    }
+}
+
+impl MessageCatalog {
+    pub fn english() -> &'static Self {
+        &ENGLISH
+    }
+
+    fn parse_templates(json: &str) -> Result<HashMap<String, String>, GameError> {
+        serde_json::from_str(json).map_err(|e| GameError::InvalidJson(e.to_string()))
+    }
+
+    /// 내장 카탈로그 (`en`, `ko`)
+    pub fn builtin(locale: &str) -> Result<Self, GameError> {
+        let (_, json) = BUILTIN_CATALOGS.iter().find(|(name, _)| *name == locale).ok_or_else(|| GameError::UnknownLocale(locale.into()))?;
+        let mut templates = if locale == "en" { HashMap::new() } else { ENGLISH.templates.clone() };
+        templates.extend(Self::parse_templates(json)?);
+        Ok(Self { locale: locale.into(), templates })
+    }
+
+    /// `templates_json`(키와 틀의 JSON 객체)으로 내장 카탈로그의 틀을 덮어쓰거나 새 로케일을 만듦
+    pub fn with_templates(locale: &str, templates_json: &str) -> Result<Self, GameError> {
+        let mut catalog = Self::builtin(locale).unwrap_or_else(|_| Self { locale: locale.into(), ..Self::default() });
+        catalog.templates.extend(Self::parse_templates(templates_json)?);
+        Ok(catalog)
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn render(&self, event: &GameEvent) -> String {
+        let key = event.message_key();
+        let Some(template) = self.templates.get(&key).or_else(|| ENGLISH.templates.get(&key)) else {
+            return key;
+        };
+        match serde_json::to_value(event) {
+            Ok(Value::Object(fields)) => Self::fill(template, &fields),
+            _ => template.clone(),
+        }
+    }
+
+    /// `{필드}`를 값으로 채움 (없는 필드는 그대로 둠)
+    fn fill(template: &str, fields: &Map<String, Value>) -> String {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            match fields.get(&after[..end]) {
+                Some(Value::String(s)) => rendered.push_str(s),
+                Some(Value::Null) => {},
+                Some(value) => rendered.push_str(&value.to_string()),
+                None => rendered.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &after[end + 1..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+impl GameEngine {
+    /// `index`번째부터의 사건을 현재 로케일로 옮긴 문장
+    pub fn rendered_events_since(&self, index: usize) -> Vec<String> {
+        self.events_since(index).iter().map(|event| self.messages.render(event)).collect()
+    }
+
+    /// 로그를 현재 로케일로 다시 옮김 (사건 없이 남은 예전 저장 파일의 로그 줄은 그대로 둠)
+    fn rerender_log(&mut self) {
+        let legacy = self.state.log.len().saturating_sub(self.state.events.len());
+        self.state.log.truncate(legacy);
+        let rendered = self.rendered_events_since(0);
+        self.state.log.extend(rendered);
+    }
+}
+
+impl GameEvent {
+    /// 메시지 카탈로그에서 이 사건의 틀을 찾는 키
+    pub fn message_key(&self) -> String {
+        let variant = match self {
+            Self::Landed { owner_id: None, .. } => Some("Unowned"),
+            Self::PurchaseOffered { building: Some(_), .. } => Some("Building"),
+            Self::CannotAfford { building: true, .. } => Some("Building"),
+            Self::PurchaseDeclined { building: true, .. } => Some("Building"),
+            Self::Bankrupt { heir_id: None, .. } => Some("Market"),
+            _ => None,
+        };
+        let name = serde_json::to_value(self).ok()
+            .and_then(|value| value.get("type").and_then(Value::as_str).map(String::from))
+            .unwrap_or_default();
+        match variant {
+            Some(variant) => format!("{}.{}", name, variant),
+            None => name,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 내장 로케일(`en`, `ko`)로 로그를 다시 옮기고 이후의 로그를 남김
+    pub fn set_locale(&mut self, locale: &str) -> Result<(), GameError> {
+        self.record(ReplayStep::SetLocale { locale: locale.into() }, |game| {
+            game.messages = MessageCatalog::builtin(locale)?;
+            game.rerender_log();
+            Ok(())
+        })
+    }
+
+    /// `templates_json`의 틀로 `locale`의 카탈로그를 만들어 로그를 다시 옮기고 이후의 로그를 남김
+    pub fn load_messages(&mut self, locale: &str, templates_json: &str) -> Result<(), GameError> {
+        self.record(ReplayStep::LoadMessages { locale: locale.into(), templates_json: templates_json.into() }, |game| {
+            game.messages = MessageCatalog::with_templates(locale, templates_json)?;
+            game.rerender_log();
+            Ok(())
+        })
+    }
+
+    pub fn get_locale(&self) -> String {
+        self.messages.locale().into()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// `index`번째부터의 사건을 현재 로케일로 옮긴 문장 (문자열 배열의 JSON)
+    pub fn render_events_since(&self, index: usize) -> String {
+        serde_json::to_string(&self.rendered_events_since(index)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, GameEvent, MessageCatalog};
+
+    #[test]
+    fn changing_the_locale_rerenders_the_log() {
+        let mut game = testing::game(&[]);
+        game.state.log.insert(0, "legacy line".into());
+        game.set_locale("ko").unwrap();
+        let korean = MessageCatalog::builtin("ko").unwrap();
+        assert_eq!(game.state.log, ["legacy line".to_string(), korean.render(&GameEvent::GameStarted)]);
+        assert_ne!(game.state.log[1], MessageCatalog::english().render(&GameEvent::GameStarted));
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, GameEngine, GameError, GameEvent, GameSituation, Ownership, ReplayStep};
+
+/// 플레이어의 동의를 기다리는 구매 제안
+///
+/// 땅과 건물은 따로 묻는다. 빈 땅에 도착하면 먼저 땅을 살지 묻고,
+/// 땅을 샀거나 이미 자기 땅이라면 다음 건물을 살지 묻는다.
+/// 플레이어가 받아들이기 전에는 돈을 받지 않는다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Purchase {
+    pub tile_name: String,
+    /// 땅값 (무료이거나 티켓을 쓰면 0), 건물을 살지 묻는 중이면 `None`
+    pub land_price: Option<i64>,
+}
+
+impl GameEngine {
+    /// 현재 플레이어에게 땅이나 다음 건물을 살지 물음
+    ///
+    /// 돈이 모자라거나 더 지을 수 없으면 묻지 않으며, 돈이 모자라 빈 땅을 사지 못하면 경매에 부친다.
+    pub(crate) fn offer_purchase(&mut self, tile_name: &str, land_price: Option<i64>) {
+        let player = &self.state.players[self.state.current_turn_idx];
+        let (player_id, money) = (player.id, player.money);
+        let offered = match land_price {
+            Some(price) if money >= price => {
+                self.emit(GameEvent::PurchaseOffered { player_id, tile_name: tile_name.into(), building: None, price });
+                true
+            },
+            Some(_) => {
+                self.emit(GameEvent::CannotAfford { player_id, tile_name: tile_name.into(), building: false });
+                self.start_auction(tile_name);
+                false
+            },
+            None => {
+                let owned = self.state.properties.get(tile_name).is_some_and(|ownership| ownership.owner_id == player_id);
+                match self.next_building_tier(tile_name) {
+                    Some(tier) if owned && money >= tier.cost => {
+                        self.emit(GameEvent::PurchaseOffered { player_id, tile_name: tile_name.into(), building: Some(tier.name), price: tier.cost });
+                        true
+                    },
+                    Some(_) if owned => {
+                        self.emit(GameEvent::CannotAfford { player_id, tile_name: tile_name.into(), building: true });
+                        false
+                    },
+                    Some(_) => false,
+                    None => {
+                        self.emit(GameEvent::BuildingLimitReached { tile_name: tile_name.into() });
+                        false
+                    }
+                }
+            }
+        };
+        if offered {
+            self.state.pending_purchase = Some(Purchase { tile_name: tile_name.into(), land_price });
+            self.now = GameSituation::PendingBuyResponse;
+        }
+    }
+
+    /// 대기 중인 구매 제안
+    ///
+    /// 제안이 저장되지 않은 이전 세이브라면 현재 플레이어가 서 있는 타일로부터 만든다.
+    pub(crate) fn current_purchase(&self) -> Purchase {
+        self.state.pending_purchase.clone().unwrap_or_else(|| {
+            let player = &self.state.players[self.state.current_turn_idx];
+            let tile = &self.state.board[player.position as usize];
+            Purchase {
+                tile_name: tile.name.clone(),
+                land_price: (!self.state.properties.contains_key(&tile.name)).then_some(tile.price),
+            }
+        })
+    }
+
+    /// 대기 중인 구매 제안
+    pub fn pending_purchase(&self) -> Option<Purchase> {
+        (self.now == GameSituation::PendingBuyResponse).then(|| self.current_purchase())
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 현재 타일의 구매 제안을 받아들임
+    ///
+    /// 땅을 샀다면 이어서 첫 건물을 살지 묻는다.
+    pub fn accept_purchase(&mut self) -> Result<(), GameError> {
+        self.expect(Command::AcceptPurchase)?;
+        self.record(ReplayStep::AcceptPurchase, |game| game.buy_current_purchase())
+    }
+
+    /// 현재 타일의 땅이나 다음 건물을 삼
+    fn buy_current_purchase(&mut self) -> Result<(), GameError> {
+        let Purchase { tile_name, land_price } = self.current_purchase();
+        let player_index = self.state.current_turn_idx;
+        let player_id = self.state.players[player_index].id;
+        let money = self.state.players[player_index].money;
+
+        match land_price {
+            Some(price) => {
+                if money < price {
+                    return Err(GameError::InsufficientFunds { required: price, available: money });
+                }
+                self.state.players[player_index].money -= price;
+                self.state.properties.insert(tile_name.clone(), Ownership { owner_id: player_id, buildings: 0, mortgaged: false });
+                self.emit(GameEvent::Bought { player_id, tile_name: tile_name.clone(), price });
+            },
+            None => {
+                let tier = self.next_building_tier(&tile_name).ok_or_else(|| GameError::BuildingLimitReached(tile_name.clone()))?;
+                if money < tier.cost {
+                    return Err(GameError::InsufficientFunds { required: tier.cost, available: money });
+                }
+                let ownership = self.state.properties.get_mut(&tile_name).filter(|ownership| ownership.owner_id == player_id).ok_or_else(|| GameError::UnownedProperty(tile_name.clone()))?;
+                ownership.buildings += 1;
+                self.state.players[player_index].money -= tier.cost;
+                self.emit(GameEvent::Built { player_id, tile_name: tile_name.clone(), building: tier.name, cost: tier.cost });
+            }
+        }
+
+        self.state.pending_purchase = None;
+        self.now = GameSituation::EndTurn;
+        if land_price.is_some() {
+            self.offer_purchase(&tile_name, None);
+        }
+        Ok(())
+    }
+
+    /// 현재 타일의 구매 제안을 거절 (빈 땅이었다면 경매에 부침)
+    pub fn decline_purchase(&mut self) -> Result<(), GameError> {
+        self.expect(Command::DeclinePurchase)?;
+        self.record(ReplayStep::DeclinePurchase, |game| {
+            let purchase = game.current_purchase();
+            let player_id = game.state.players[game.state.current_turn_idx].id;
+            game.emit(GameEvent::PurchaseDeclined { player_id, tile_name: purchase.tile_name.clone(), building: purchase.land_price.is_none() });
+            game.state.pending_purchase = None;
+            game.now = GameSituation::EndTurn;
+            if purchase.land_price.is_some() {
+                game.start_auction(&purchase.tile_name);
+            }
+            Ok(())
+        })
+    }
+
+    /// `accept_purchase`의 이전 이름 (`pos`는 현재 플레이어의 위치여야 함)
+    pub fn buy(&mut self, pos: u32) -> Result<(), GameError> {
+        Self::check_position(&self.state.board, pos)?;
+        if pos != self.state.players[self.state.current_turn_idx].position {
+            return Err(GameError::InvalidPosition(pos));
+        }
+        self.accept_purchase()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 대기 중인 구매 제안 (`Purchase`의 JSON, 없으면 `null`)
+    pub fn get_pending_purchase(&self) -> String {
+        serde_json::to_string(&self.pending_purchase()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, DicePair, GameEngine, GameEvent, GameSituation, Purchase, ScriptedDice};
+
+    /// 플레이어 1이 빈 땅인 부산에 도착해 살지 묻는 중인 게임
+    fn offered_busan(consts: &[(&str, u32)]) -> GameEngine {
+        let mut game = testing::game(consts);
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        let scripts = testing::scripts();
+        game.run_turn_script(&scripts.action, DicePair(1, 1), &scripts.cycle).unwrap();
+        game
+    }
+
+    #[test]
+    fn buying_land_offers_the_first_building() {
+        let mut game = offered_busan(&[]);
+        assert_eq!(game.pending_purchase(), Some(Purchase { tile_name: "Busan".into(), land_price: Some(200_000) }));
+        assert_eq!(game.state.players[0].money, 1_000_000);
+
+        game.accept_purchase().unwrap();
+        assert_eq!(game.state.properties["Busan"].owner_id, 1);
+        assert_eq!(game.state.players[0].money, 800_000);
+        assert_eq!(game.pending_purchase(), Some(Purchase { tile_name: "Busan".into(), land_price: None }));
+
+        game.accept_purchase().unwrap();
+        assert_eq!(game.state.properties["Busan"].buildings, 1);
+        assert_eq!(game.state.players[0].money, 700_000);
+        assert_eq!(game.now, GameSituation::EndTurn);
+        assert!(game.state.events.contains(&GameEvent::Built { player_id: 1, tile_name: "Busan".into(), building: "Building".into(), cost: 100_000 }));
+    }
+
+    #[test]
+    fn declined_land_goes_to_auction_when_enabled() {
+        let mut game = offered_busan(&[("AUCTION_ENABLED", 1)]);
+        game.decline_purchase().unwrap();
+        assert_eq!(game.now, GameSituation::PendingAuction);
+        assert_eq!(game.auction().map(|auction| auction.tile_name.as_str()), Some("Busan"));
+        assert!(!game.state.properties.contains_key("Busan"));
+    }
+
+    #[test]
+    fn declined_land_stays_unowned_without_auction() {
+        let mut game = offered_busan(&[]);
+        game.decline_purchase().unwrap();
+        assert_eq!(game.now, GameSituation::EndTurn);
+        assert!(game.auction().is_none());
+        assert!(!game.state.properties.contains_key("Busan"));
+        assert_eq!(game.state.players[0].money, 1_000_000);
+        assert!(game.state.events.contains(&GameEvent::PurchaseDeclined { player_id: 1, tile_name: "Busan".into(), building: false }));
+    }
+}
@@ -0,0 +1,261 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameEngine, Player};
+
+/// 게임이 끝난 이유
+///
+/// 종료 조건은 `consts`로 설정한다.
+/// - `CYCLE_LIMIT` (기본 4): 남은 플레이어가 모두 이 바퀴 수를 채우면 종료, 0이면 사용하지 않음
+/// - `TURN_LIMIT` (기본 0): 전체 턴 수가 이 값에 이르면 종료, 0이면 사용하지 않음
+/// - `TARGET_NET_WORTH` (기본 0): 누군가의 순자산이 이 값에 이르면 종료, 0이면 사용하지 않음
+/// - `LAST_PLAYER_STANDING` (기본 1): 파산하지 않은 플레이어가 한 명만 남으면 종료, 0이면 사용하지 않음
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum EndReason {
+    CycleLimit,
+    TurnLimit,
+    TargetNetWorth { player_id: u32 },
+    LastPlayerStanding { player_id: u32 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RankingEntry {
+    pub rank: u32,
+    pub player_id: u32,
+    pub net_worth: i64,
+    pub money: i64,
+    pub bankrupt: bool,
+}
+
+impl GameEngine {
+    /// 현금 + 부동산(땅값과 건물 값, 저당 잡힌 금액 제외) - 남은 대출 + 티켓(`TICKET_VALUE`, 기본 0)
+    pub fn net_worth(&self, player: &Player) -> i64 {
+        let properties = self.state.properties.iter().filter(|(_, ownership)| ownership.owner_id == player.id).map(|(name, ownership)| {
+            let land = self.tile_by_name(name).map(|tile| tile.price).unwrap_or(0);
+            let mortgage = if ownership.mortgaged { self.mortgage_value(name).unwrap_or(0) } else { 0 };
+            land + self.buildings_value(name, ownership.buildings) - mortgage
+        }).sum::<i64>();
+        let loans = player.remaining_loans.iter().map(|loan| loan.amount).sum::<i64>();
+        let tickets = player.tickets_count;
+        let tickets_count = tickets.free_hospital + tickets.free_property + tickets.double_lotto + tickets.no_tax + tickets.release_from_jail + tickets.bonus;
+        player.money + properties - loans + tickets_count as i64 * self.const_or("TICKET_VALUE", 0) as i64
+    }
+
+    /// 다음 차례가 될 파산하지 않은 플레이어의 인덱스 (없으면 현재 인덱스)
+    pub(crate) fn next_active_player_idx(&self) -> usize {
+        let players_count = self.state.players.len();
+        (1..=players_count).map(|offset| (self.state.current_turn_idx + offset) % players_count)
+            .find(|&idx| !self.state.players[idx].bankrupt)
+            .unwrap_or(self.state.current_turn_idx)
+    }
+
+    /// 현재 플레이어로부터 파산하지 않은 플레이어만 세어 `steps`번째 뒤 플레이어의 인덱스
+    pub(crate) fn active_player_idx_after(&self, steps: usize) -> usize {
+        let active = (0..self.state.players.len()).filter(|&idx| !self.state.players[idx].bankrupt).collect::<Vec<_>>();
+        let current = active.iter().position(|&idx| idx == self.state.current_turn_idx).unwrap_or(0);
+        active.get((current + steps) % active.len().max(1)).copied().unwrap_or(self.state.current_turn_idx)
+    }
+
+    pub(crate) fn check_end_condition(&self) -> Option<EndReason> {
+        let active = self.state.players.iter().filter(|player| !player.bankrupt).collect::<Vec<_>>();
+
+        if self.const_or("LAST_PLAYER_STANDING", 1) > 0 && self.state.players.len() > 1 && active.len() <= 1 {
+            return Some(EndReason::LastPlayerStanding { player_id: active.first().map(|player| player.id).unwrap_or(0) });
+        }
+        let target = self.const_or("TARGET_NET_WORTH", 0) as i64;
+        if target > 0 && let Some(player) = active.iter().find(|player| self.net_worth(player) >= target) {
+            return Some(EndReason::TargetNetWorth { player_id: player.id });
+        }
+        let turn_limit = self.const_or("TURN_LIMIT", 0);
+        if turn_limit > 0 && self.state.turn_count >= turn_limit {
+            return Some(EndReason::TurnLimit);
+        }
+        let cycle_limit = self.const_or("CYCLE_LIMIT", 4);
+        if cycle_limit > 0 && active.iter().all(|player| player.cycles >= cycle_limit) {
+            return Some(EndReason::CycleLimit);
+        }
+        None
+    }
+
+    /// 순위: 남은 플레이어는 순자산 순, 파산한 플레이어는 늦게 파산한 순
+    pub fn ranking(&self) -> Vec<RankingEntry> {
+        let mut players = self.state.players.iter().collect::<Vec<_>>();
+        let elimination_order = |player: &Player| self.state.eliminations.iter().position(|&pid| pid == player.id);
+        players.sort_by(|a, b| {
+            match (a.bankrupt, b.bankrupt) {
+                (false, false) => self.net_worth(b).cmp(&self.net_worth(a)),
+                (true, true) => elimination_order(b).cmp(&elimination_order(a)),
+                (bankrupt_a, bankrupt_b) => bankrupt_a.cmp(&bankrupt_b),
+            }.then(a.id.cmp(&b.id))
+        });
+        players.into_iter().enumerate().map(|(i, player)| RankingEntry {
+            rank: (i + 1) as u32,
+            player_id: player.id,
+            net_worth: self.net_worth(player),
+            money: player.money,
+            bankrupt: player.bankrupt,
+        }).collect()
+    }
+
+    /// 게임이 끝난 이유 (진행 중이면 `None`)
+    pub fn end_reason(&self) -> Option<EndReason> {
+        self.state.end_reason
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 게임이 끝났다면 1위 플레이어의 id
+    pub fn get_winner(&self) -> Option<u32> {
+        self.state.end_reason.and(self.ranking().first().map(|entry| entry.player_id))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    /// 현재 순위 (`RankingEntry` 배열의 JSON)
+    pub fn get_ranking(&self) -> String {
+        serde_json::to_string(&self.ranking()).unwrap()
+    }
+
+    /// 게임이 끝난 이유 (`EndReason`의 JSON, 진행 중이면 `null`)
+    pub fn get_end_reason(&self) -> String {
+        serde_json::to_string(&self.end_reason()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, Creditor, DicePair, EndReason, GameEngine, GameSituation, Ownership, ScriptedDice};
+
+    #[test]
+    fn twist_of_fate_skips_bankrupt_players() {
+        let mut game = testing::players_game(3, &[]);
+        game.state.players[1].bankrupt = true;
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 0, mortgaged: false });
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 3, buildings: 0, mortgaged: false });
+        game.pending_chance_card_id = Some("twist".into());
+        game.now = GameSituation::PendingCheckChanceCardResponse;
+
+        let scripts = testing::scripts();
+        game.check_chance_card(r#"#{ type: "TwistOfFate", dice_a: 1, dice_b: 0 }"#, &scripts.cycle, None).unwrap();
+        assert_eq!(game.state.properties["Seoul"].owner_id, 3);
+        assert_eq!(game.state.properties["Busan"].owner_id, 1);
+    }
+
+    #[test]
+    fn bankrupt_players_are_not_paid() {
+        let mut game = testing::players_game(3, &[]);
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        game.state.players[1].bankrupt = true;
+        game.state.players[1].money = 0;
+
+        let scripts = testing::scripts();
+        game.run_turn_script(r#"#{ type: "PayToAll", amount: 10000 }"#, DicePair(1, 2), &scripts.cycle).unwrap();
+        let money = game.state.players.iter().map(|player| player.money).collect::<Vec<_>>();
+        assert_eq!(money, [980_000, 0, 1_010_000]);
+        assert_eq!(game.state.government_income, 10_000);
+    }
+
+    #[test]
+    fn bankrupt_players_get_no_basic_income() {
+        let mut game = testing::players_game(3, &[]);
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        game.state.players[0].position = 5;
+        game.state.players[1].bankrupt = true;
+        game.state.players[1].money = 0;
+
+        let cycle = r#"#{ new_government_income: government_income, remaining_salary: 0, basic_income: 5000 }"#;
+        game.run_turn_script(r#"#{ type: "Log", message: "" }"#, DicePair(1, 2), cycle).unwrap();
+        let money = game.state.players.iter().map(|player| player.money).collect::<Vec<_>>();
+        assert_eq!(money, [1_005_000, 0, 1_005_000]);
+    }
+
+    /// 현재 플레이어가 `creditor`에게 갚지 못할 빚을 지고 파산한 뒤 턴을 넘김
+    fn eliminate_current_player(game: &mut GameEngine, creditor: Creditor) {
+        let idx = game.state.current_turn_idx;
+        game.state.players[idx].money = -500_000;
+        game.prompt_financial_crisis(creditor);
+        game.declare_bankruptcy().unwrap();
+        game.end_turn().unwrap();
+    }
+
+    /// 현재 턴을 끝내고 다음 턴으로 넘어감
+    fn pass_turn(game: &mut GameEngine) {
+        game.now = GameSituation::EndTurn;
+        game.end_turn().unwrap();
+    }
+
+    #[test]
+    fn bankrupt_player_is_skipped_and_the_last_one_standing_wins() {
+        let mut game = testing::players_game(3, &[]);
+        eliminate_current_player(&mut game, Creditor::Market);
+        assert_eq!(game.state.current_turn_idx, 1);
+        assert_eq!(game.state.eliminations, [1]);
+        assert_eq!(game.end_reason(), None);
+        assert_eq!(game.get_winner(), None);
+
+        pass_turn(&mut game);
+        assert_eq!(game.state.current_turn_idx, 2);
+        pass_turn(&mut game);
+        assert_eq!(game.state.current_turn_idx, 1);
+
+        eliminate_current_player(&mut game, Creditor::Player(3));
+        assert_eq!(game.state.eliminations, [1, 2]);
+        assert_eq!(game.end_reason(), Some(EndReason::LastPlayerStanding { player_id: 3 }));
+        assert_eq!(game.now, GameSituation::EndGame);
+        assert_eq!(game.get_winner(), Some(3));
+    }
+
+    #[test]
+    fn game_ends_at_the_turn_limit() {
+        let mut game = testing::game(&[("TURN_LIMIT", 2)]);
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), None);
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), Some(EndReason::TurnLimit));
+        assert_eq!(game.now, GameSituation::EndGame);
+    }
+
+    #[test]
+    fn game_ends_when_every_remaining_player_completes_the_cycles() {
+        let mut game = testing::players_game(3, &[("CYCLE_LIMIT", 2)]);
+        game.state.players[0].cycles = 2;
+        game.state.players[1].bankrupt = true;
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), None);
+
+        game.state.players[2].cycles = 2;
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), Some(EndReason::CycleLimit));
+    }
+
+    #[test]
+    fn game_ends_when_someone_reaches_the_target_net_worth() {
+        let mut game = testing::game(&[("TARGET_NET_WORTH", 1_200_000)]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 0, mortgaged: false });
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), None);
+
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 2, buildings: 0, mortgaged: false });
+        pass_turn(&mut game);
+        assert_eq!(game.end_reason(), Some(EndReason::TargetNetWorth { player_id: 2 }));
+        assert_eq!(game.get_winner(), Some(2));
+    }
+
+    #[test]
+    fn ranking_puts_players_by_net_worth_then_the_latest_eliminated() {
+        let mut game = testing::players_game(4, &[]);
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 3, buildings: 0, mortgaged: false });
+        eliminate_current_player(&mut game, Creditor::Market);
+        eliminate_current_player(&mut game, Creditor::Market);
+
+        let ranking = game.ranking();
+        let order = ranking.iter().map(|entry| (entry.rank, entry.player_id, entry.bankrupt)).collect::<Vec<_>>();
+        assert_eq!(order, [(1, 3, false), (2, 4, false), (3, 2, true), (4, 1, true)]);
+        assert_eq!(ranking[0].net_worth, 1_100_000);
+    }
+}
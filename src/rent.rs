@@ -0,0 +1,140 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Creditor, GameEngine, GameError, GameEvent};
+
+impl GameEngine {
+    /// 타일 소유자와 지금 받을 통행료
+    ///
+    /// 기본 통행료는 `Tile.rent[건물 수]`(표보다 건물이 많으면 마지막 값)이고,
+    /// 표가 비어 있으면 땅값의 `RENT_PERCENT`(기본 10%)에 (건물 수 + 1)을 곱한다.
+    /// 여기에 퍼센트 단위 배율을 차례로 곱한다.
+    /// - `MEGACITY_RENT_PERCENT` (기본 200): 대도시
+    /// - `COASTAL_RENT_PERCENT` (기본 150): 해안 도시
+    /// - `PANDEMIC_RENT_PERCENT` (기본 50): 전염병이 도는 동안
+    /// - `CATASTROPHE_RENT_PERCENT` (기본 50): 재난이 진행되는 동안
+    ///
+    /// 아무도 소유하지 않았거나, 저당 잡혔거나, 소유자가 파산했다면 `None`
+    pub fn rent_of(&self, tile_name: &str) -> Option<(u32, i64)> {
+        let ownership = self.state.properties.get(tile_name).filter(|ownership| !ownership.mortgaged)?;
+        if self.state.players.iter().any(|player| player.id == ownership.owner_id && player.bankrupt) {
+            return None;
+        }
+        let tile = self.state.board.iter().find(|tile| tile.name == tile_name)?;
+
+        let buildings = ownership.buildings as usize;
+        let base = match tile.rent.as_slice() {
+            [] => tile.price * self.const_or("RENT_PERCENT", 10) as i64 / 100 * (buildings as i64 + 1),
+            table => table[buildings.min(table.len() - 1)],
+        };
+        let modifiers = [
+            (tile.is_megacity, "MEGACITY_RENT_PERCENT", 200),
+            (tile.is_coastal, "COASTAL_RENT_PERCENT", 150),
+            (self.state.pandemic_counter > 0, "PANDEMIC_RENT_PERCENT", 50),
+            (self.state.catastrophe_counter > 0, "CATASTROPHE_RENT_PERCENT", 50),
+        ];
+        let rent = modifiers.iter().filter(|&&(applies, _, _)| applies).fold(base, |rent, &(_, key, default)| {
+            rent * self.const_or(key, default) as i64 / 100
+        });
+        Some((ownership.owner_id, rent))
+    }
+
+    /// 통행료를 소유자에게 넘기고, 현금이 모자라면 소유자를 채권자로 재정 위기에 빠짐
+    pub(crate) fn pay_rent(&mut self, player_index: usize, tile_name: &str) -> Result<(), GameError> {
+        let payer_id = self.state.players[player_index].id;
+        let Some((owner_id, rent)) = self.rent_of(tile_name).filter(|&(owner_id, _)| owner_id != payer_id) else {
+            self.emit(GameEvent::NoRentDue { tile_name: tile_name.into() });
+            return Ok(());
+        };
+        let owner_mut = self.state.players.iter_mut().find(|player| player.id == owner_id).ok_or(GameError::UnknownPlayer(owner_id))?;
+        owner_mut.money += rent;
+        self.state.players[player_index].money -= rent;
+        self.emit(GameEvent::PaidRent { player_id: payer_id, owner_id, tile_name: tile_name.into(), amount: rent });
+
+        if self.state.players[player_index].money < 0 {
+            self.prompt_financial_crisis(Creditor::Player(owner_id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 지금 이 타일에 도착하면 내야 할 통행료 (받을 사람이 없으면 0)
+    pub fn get_rent(&self, tile_name: &str) -> Result<i64, GameError> {
+        if !self.state.board.iter().any(|tile| tile.name == tile_name) {
+            return Err(GameError::TileNotFound(tile_name.into()));
+        }
+        Ok(self.rent_of(tile_name).map(|(_, rent)| rent).unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, Creditor, DicePair, GameEngine, GameEvent, GameSituation, Ownership, ScriptedDice};
+
+    /// 플레이어 2가 부산에 건물 `buildings`채를 가진 게임
+    fn owned_busan(consts: &[(&str, u32)], buildings: u32) -> GameEngine {
+        let mut game = testing::game(consts);
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 2, buildings, mortgaged: false });
+        game
+    }
+
+    #[test]
+    fn rent_grows_with_buildings_and_follows_the_table() {
+        let mut game = owned_busan(&[("RENT_PERCENT", 5)], 2);
+        assert_eq!(game.rent_of("Busan"), Some((2, 30_000)));
+
+        game.state.board[2].rent = vec![1_000, 2_000];
+        assert_eq!(game.rent_of("Busan"), Some((2, 2_000)));
+        assert_eq!(game.rent_of("Seoul"), None);
+    }
+
+    #[test]
+    fn rent_modifiers_multiply_in_turn() {
+        let mut game = owned_busan(&[("PANDEMIC_RENT_PERCENT", 10)], 0);
+        game.state.board[2].is_megacity = true;
+        game.state.board[2].is_coastal = true;
+        assert_eq!(game.rent_of("Busan"), Some((2, 60_000)));
+
+        game.state.pandemic_counter = 1;
+        game.state.catastrophe_counter = 1;
+        assert_eq!(game.rent_of("Busan"), Some((2, 3_000)));
+    }
+
+    #[test]
+    fn no_rent_on_mortgaged_tiles_or_for_bankrupt_owners() {
+        let mut game = owned_busan(&[], 0);
+        game.state.properties.get_mut("Busan").unwrap().mortgaged = true;
+        assert_eq!(game.rent_of("Busan"), None);
+        assert_eq!(game.get_rent("Busan"), Ok(0));
+
+        game.state.properties.get_mut("Busan").unwrap().mortgaged = false;
+        game.state.players[1].bankrupt = true;
+        assert_eq!(game.rent_of("Busan"), None);
+    }
+
+    #[test]
+    fn pay_rent_moves_money_from_the_payer_to_the_owner() {
+        let mut game = owned_busan(&[], 0);
+        let scripts = testing::scripts();
+        game.run_turn_script(&scripts.action, DicePair(1, 1), &scripts.cycle).unwrap();
+
+        assert_eq!(game.state.players[0].money, 980_000);
+        assert_eq!(game.state.players[1].money, 1_020_000);
+        assert!(game.state.events.contains(&GameEvent::PaidRent { player_id: 1, owner_id: 2, tile_name: "Busan".into(), amount: 20_000 }));
+    }
+
+    #[test]
+    fn rent_beyond_the_payers_money_starts_a_crisis_owed_to_the_owner() {
+        let mut game = owned_busan(&[], 0);
+        game.state.players[0].money = 5_000;
+        let scripts = testing::scripts();
+        game.run_turn_script(&scripts.action, DicePair(1, 1), &scripts.cycle).unwrap();
+
+        assert_eq!(game.state.players[0].money, -15_000);
+        assert_eq!(game.state.crisis_creditor, Some(Creditor::Player(2)));
+        assert_eq!(game.now, GameSituation::PendingFinancialCrisisResponse);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::VecDeque;
+
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{DicePair, DiceSource, GameEngine, GameError, GameRng, GameSnapshot, MessageCatalog, SaveFile, SeededDice, TicketCount, TradeOffer};
+use crate::save::state_hash;
+
+/// 엔진에 적용된 명령 하나와 그 인자
+///
+/// `command` 필드로 구분되며, 이름은 해당 명령을 수행하는 `GameEngine`의 메서드 이름을 따른다.
+/// 다른 명령을 조합한 메서드(`roll_and_run_turn_script` 등)는 조합된 명령들로 기록된다.
+/// 스크립트는 `Replay.scripts`에 한 번씩만 담고, 명령에는 그 위치를 남긴다.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ReplayStep {
+    /// `seeded`면 게임 상태의 난수 생성기로 굴린 주사위
+    RollDice { dices: DicePair, seeded: bool },
+    RunTurnScript { script_action: usize, dices: DicePair, script_cycle: usize },
+    AcceptPurchase,
+    DeclinePurchase,
+    UseTicket { to_use: TicketCount, script_action: usize, script_cycle: usize },
+    LuckTest { init_double_lotto: bool },
+    StopLuckTest,
+    TryToJailbreakByDices { dices: DicePair },
+    TryToJailbreakByMoney,
+    GiveUpJailbreak,
+    GetRandomChanceCard,
+    CheckChanceCard { script_chance_action: usize, script_cycle: usize, payload_json: Option<String> },
+    BorrowMoney { player_id: u32, amount: i64 },
+    RepayLoan { player_id: u32, loan_id: u32, amount: i64 },
+    SellBuilding { tile_name: String },
+    MortgageProperty { tile_name: String },
+    RedeemMortgage { tile_name: String },
+    ResolveFinancialCrisis,
+    DeclareBankruptcy,
+    ProposeTrade { from: u32, to: u32, offer: TradeOffer },
+    AcceptTrade,
+    RejectTrade,
+    CounterTrade { offer: TradeOffer },
+    PlaceBid { player_id: u32, amount: i64 },
+    PassBid { player_id: u32 },
+    CloseAuction,
+    EndTurn,
+    /// 로그 문장이 로케일에 따라 달라지므로 함께 기록
+    SetLocale { locale: String },
+    LoadMessages { locale: String, templates_json: String },
+}
+
+/// 시작 상태와 그 뒤에 적용된 명령들을 담은 리플레이 문서
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    pub engine_version: String,
+    /// 게임 상태의 난수 생성기 시드 (`GameRng`처럼 문자열로 직렬화)
+    pub seed: String,
+    /// 기록을 시작한 시점의 세이브 파일
+    pub initial: SaveFile,
+    /// 기록을 시작한 시점의 로케일 (내장 로케일이 아니면 영어로 다시 실행)
+    #[serde(default)]
+    pub locale: String,
+    /// 명령들이 위치로 가리키는 스크립트
+    #[serde(default)]
+    pub scripts: Vec<String>,
+    pub steps: Vec<ReplayStep>,
+    /// 모든 명령을 적용한 뒤의 `get_state_hash`
+    pub final_state_hash: String,
+}
+
+/// 엔진이 만들어지거나 상태를 불러온 뒤로 적용된 명령의 기록
+pub(crate) struct Recorder {
+    initial: SaveFile,
+    locale: String,
+    scripts: Vec<String>,
+    steps: Vec<ReplayStep>,
+}
+
+impl Recorder {
+    pub(crate) fn new(snapshot: &GameSnapshot, messages: &MessageCatalog) -> Self {
+        Self { initial: SaveFile::new(snapshot), locale: messages.locale().into(), scripts: Vec::new(), steps: Vec::new() }
+    }
+}
+
+/// 리플레이에 기록된 주사위를 그대로 내놓는 주사위
+///
+/// 원래 시드로 굴린 주사위였다면 난수 생성기도 똑같이 소비하고, 다른 눈이 나오면 오류를 낸다.
+struct ReplayDice {
+    rolls: VecDeque<(DicePair, bool)>,
+}
+
+impl DiceSource for ReplayDice {
+    fn roll(&mut self, rng: &mut GameRng) -> Result<DicePair, GameError> {
+        let (dices, seeded) = self.rolls.pop_front().ok_or_else(|| GameError::DiceUnavailable("The replay has no more dice rolls.".into()))?;
+        if seeded {
+            let rolled = SeededDice.roll(rng)?;
+            if rolled != dices {
+                return Err(GameError::DiceUnavailable(format!("The replay diverged: rolled ({}, {}) instead of ({}, {}).", rolled.0, rolled.1, dices.0, dices.1)));
+            }
+        }
+        Ok(dices)
+    }
+
+    fn is_seeded(&self) -> bool {
+        self.rolls.front().is_some_and(|&(_, seeded)| seeded)
+    }
+
+    /// 기록된 명령은 원래 게임에서 이미 받아들여진 것
+    fn accepts_host_dice(&self) -> bool {
+        true
+    }
+}
+
+impl GameEngine {
+    /// 현재 상태에서 기록을 새로 시작 (지금까지의 기록은 버림)
+    pub(crate) fn start_recording(&mut self) {
+        self.recorder = Recorder::new(&self.snapshot(), &self.messages);
+    }
+
+    /// 명령이 가리킬 스크립트의 위치 (처음 보는 스크립트면 기록에 추가)
+    pub(crate) fn intern_script(&mut self, script: &str) -> usize {
+        let scripts = &mut self.recorder.scripts;
+        scripts.iter().position(|recorded| recorded == script).unwrap_or_else(|| {
+            scripts.push(script.into());
+            scripts.len() - 1
+        })
+    }
+
+    /// 명령을 실행하고, 성공했을 때만 되돌리기 항목과 리플레이 기록에 남김
+    ///
+    /// 실패한 명령이 중간까지 바꾼 상태는 모두 되돌리므로, 실패한 명령은 엔진에 흔적을 남기지 않는다.
+    pub(crate) fn record<T>(&mut self, step: ReplayStep, run: impl FnOnce(&mut Self) -> Result<T, GameError>) -> Result<T, GameError> {
+        let before = self.snapshot();
+        match run(self) {
+            Ok(result) => {
+                let before = self.is_undoable(&step).then_some(before);
+                self.push_step(step, before);
+                Ok(result)
+            },
+            Err(e) => {
+                self.rollback(before);
+                Err(e)
+            }
+        }
+    }
+
+    /// 성공한 명령을 기록 (`before`는 명령을 적용하기 직전의 상태)
+    pub(crate) fn push_step(&mut self, step: ReplayStep, before: Option<GameSnapshot>) {
+        self.push_undo(before);
+        self.recorder.steps.push(step);
+    }
+
+    /// 되돌린 명령들을 기록에서 지움
+    pub(crate) fn unrecord(&mut self, count: usize) -> Vec<ReplayStep> {
+        let start = self.recorder.steps.len().saturating_sub(count);
+        self.recorder.steps.split_off(start)
+    }
+
+    /// 다시 실행한 명령들을 기록에 되돌려 놓음
+    pub(crate) fn rerecord(&mut self, steps: Vec<ReplayStep>) {
+        self.recorder.steps.extend(steps);
+    }
+
+    pub fn replay_document(&self) -> Replay {
+        Replay {
+            engine_version: env!("CARGO_PKG_VERSION").into(),
+            seed: self.state.rng.seed().to_string(),
+            initial: self.recorder.initial.clone(),
+            locale: self.recorder.locale.clone(),
+            scripts: self.recorder.scripts.clone(),
+            steps: self.recorder.steps.clone(),
+            final_state_hash: self.get_state_hash(),
+        }
+    }
+
+    /// 리플레이의 시작 상태에서 처음 `step_count`개의 명령을 다시 실행
+    ///
+    /// 실패한 명령은 상태를 되돌리고 기록하지 않으므로(`record`), 다시 실행하다 실패하는 명령이 있으면
+    /// 리플레이가 어긋난 것이며 그 오류를 반환한다.
+    pub fn replay_steps(replay: &Replay, step_count: usize) -> Result<GameEngine, GameError> {
+        let snapshot = replay.initial.clone().into_current_snapshot()?;
+        let mut game = Self::from_parts(snapshot.state, snapshot.salary, snapshot.building_cost, snapshot.now, snapshot.pending_chance_card_id);
+        if let Ok(messages) = MessageCatalog::builtin(&replay.locale) {
+            game.messages = messages;
+            game.start_recording();
+        }
+        let steps = &replay.steps[..step_count.min(replay.steps.len())];
+        let rolls = steps.iter().filter_map(|step| match step {
+            ReplayStep::RollDice { dices, seeded } => Some((*dices, *seeded)),
+            _ => None,
+        }).collect();
+        game.dice_source = Box::new(ReplayDice { rolls });
+        for step in steps {
+            game.apply_step(step, &replay.scripts)?;
+        }
+        game.dice_source = Box::new(SeededDice);
+        Ok(game)
+    }
+
+    fn apply_step(&mut self, step: &ReplayStep, scripts: &[String]) -> Result<(), GameError> {
+        let script = |index: &usize| scripts.get(*index).map(String::as_str).ok_or_else(|| GameError::InvalidReplay(format!("The replay has no script {}.", index)));
+        match step {
+            ReplayStep::RollDice { .. } => self.roll_dice().map(|_| ()),
+            ReplayStep::RunTurnScript { script_action, dices, script_cycle } => self.run_turn_script(script(script_action)?, *dices, script(script_cycle)?),
+            ReplayStep::AcceptPurchase => self.accept_purchase(),
+            ReplayStep::DeclinePurchase => self.decline_purchase(),
+            ReplayStep::UseTicket { to_use, script_action, script_cycle } => self.use_ticket(*to_use, script(script_action)?, script(script_cycle)?),
+            ReplayStep::LuckTest { init_double_lotto } => self.luck_test(*init_double_lotto),
+            ReplayStep::StopLuckTest => self.stop_luck_test(),
+            ReplayStep::TryToJailbreakByDices { dices } => self.try_to_jailbreak_by_dices(*dices),
+            ReplayStep::TryToJailbreakByMoney => self.try_to_jailbreak_by_money(),
+            ReplayStep::GiveUpJailbreak => self.give_up_jailbreak(),
+            ReplayStep::GetRandomChanceCard => self.get_random_chance_card(),
+            ReplayStep::CheckChanceCard { script_chance_action, script_cycle, payload_json } => self.check_chance_card(script(script_chance_action)?, script(script_cycle)?, payload_json.clone()),
+            ReplayStep::BorrowMoney { player_id, amount } => self.borrow_money(*player_id, *amount),
+            ReplayStep::RepayLoan { player_id, loan_id, amount } => self.repay_loan(*player_id, *loan_id, *amount),
+            ReplayStep::SellBuilding { tile_name } => self.sell_building(tile_name),
+            ReplayStep::MortgageProperty { tile_name } => self.mortgage_property(tile_name),
+            ReplayStep::RedeemMortgage { tile_name } => self.redeem_mortgage(tile_name),
+            ReplayStep::ResolveFinancialCrisis => self.resolve_financial_crisis(),
+            ReplayStep::DeclareBankruptcy => self.declare_bankruptcy(),
+            ReplayStep::ProposeTrade { from, to, offer } => self.propose_trade(*from, *to, offer.clone()),
+            ReplayStep::AcceptTrade => self.accept_trade(),
+            ReplayStep::RejectTrade => self.reject_trade(),
+            ReplayStep::CounterTrade { offer } => self.counter_trade(offer.clone()),
+            ReplayStep::PlaceBid { player_id, amount } => self.place_bid(*player_id, *amount),
+            ReplayStep::PassBid { player_id } => self.pass_bid(*player_id),
+            ReplayStep::CloseAuction => self.close_auction(),
+            ReplayStep::EndTurn => self.end_turn(),
+            ReplayStep::SetLocale { locale } => self.set_locale(locale),
+            ReplayStep::LoadMessages { locale, templates_json } => self.load_messages(locale, templates_json),
+        }
+    }
+
+    /// 리플레이를 끝까지 다시 실행하고, 마지막 상태가 기록과 같은지 확인
+    pub fn replay(replay: &Replay) -> Result<GameEngine, GameError> {
+        let game = Self::replay_steps(replay, replay.steps.len())?;
+        let actual = game.get_state_hash();
+        if actual != replay.final_state_hash {
+            return Err(GameError::ReplayMismatch { expected: replay.final_state_hash.clone(), actual });
+        }
+        Ok(game)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 스냅샷의 해시 (키 순서와 무관하게 같은 상태면 같은 값)
+    pub fn get_state_hash(&self) -> String {
+        state_hash(&self.snapshot())
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    fn parse_replay(replay_json: &str) -> Result<Replay, GameError> {
+        serde_json::from_str(replay_json).map_err(|e| GameError::InvalidReplay(e.to_string()))
+    }
+
+    /// 엔진이 만들어지거나 상태를 불러온 뒤로 적용된 모든 명령 (`Replay`의 JSON)
+    pub fn get_replay(&self) -> String {
+        serde_json::to_string(&self.replay_document()).unwrap()
+    }
+
+    /// `replay` (`replay_json`은 `get_replay`로 받은 JSON)
+    #[wasm_bindgen(js_name = replay)]
+    pub fn replay_json(replay_json: &str) -> Result<GameEngine, GameError> {
+        Self::replay(&Self::parse_replay(replay_json)?)
+    }
+
+    /// 다시 보기용: 리플레이의 처음 `step_count`개의 명령까지만 다시 실행 (확인하지 않음)
+    pub fn replay_until(replay_json: &str, step_count: usize) -> Result<GameEngine, GameError> {
+        Self::replay_steps(&Self::parse_replay(replay_json)?, step_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, BuiltinStrategy, DicePair, GameEngine, GameError, GameSituation, ReplayStep, ScriptedDice};
+
+    /// 탐욕 전략의 봇 둘이 `steps`개의 명령을 내린 게임
+    fn bot_game(steps: usize) -> GameEngine {
+        let mut game = testing::game(&[]);
+        let scripts = testing::scripts();
+        for player_id in [1, 2] {
+            game.set_builtin_bot(player_id, BuiltinStrategy::Greedy, 0).unwrap();
+        }
+        for _ in 0..steps {
+            if game.step_bot(&scripts).unwrap().is_none() {
+                break;
+            }
+        }
+        game
+    }
+
+    #[test]
+    fn recorded_game_replays_to_the_same_state() {
+        let game = bot_game(200);
+        let replay = game.replay_document();
+        assert!(replay.steps.iter().any(|step| matches!(step, ReplayStep::RunTurnScript { .. })));
+        // 같은 스크립트는 한 번만 담김
+        assert_eq!(replay.scripts.len(), 2);
+
+        let replayed = GameEngine::replay(&replay).unwrap();
+        assert_eq!(replayed.get_state_hash(), game.get_state_hash());
+    }
+
+    #[test]
+    fn replay_fails_at_the_first_failing_step() {
+        let mut replay = bot_game(20).replay_document();
+        replay.steps.insert(0, ReplayStep::AcceptPurchase);
+        assert!(matches!(GameEngine::replay(&replay), Err(GameError::InvalidSituation { .. })));
+
+        let mut replay = bot_game(20).replay_document();
+        replay.scripts.clear();
+        assert!(matches!(GameEngine::replay(&replay), Err(GameError::InvalidReplay(_))));
+    }
+
+    #[test]
+    fn failed_command_leaves_the_engine_as_it_was() {
+        let mut game = testing::game(&[]);
+        let scripts = testing::scripts();
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        let hash = game.get_state_hash();
+
+        // 움직인 뒤 스크립트 결과가 잘못되어 실패
+        let result = game.run_turn_script(r#"#{ type: "PayTax" }"#, DicePair(1, 2), &scripts.cycle);
+        assert!(matches!(result, Err(GameError::InvalidScriptResult(_))));
+        assert_eq!(game.get_state_hash(), hash);
+        assert_eq!(game.get_situation(), GameSituation::PendingRollResponse);
+        assert!(game.replay_document().steps.is_empty());
+
+        game.run_turn_script(&scripts.action, DicePair(1, 2), &scripts.cycle).unwrap();
+        assert_eq!(game.state().current_player().position, 3);
+    }
+
+    #[test]
+    fn game_with_a_failed_command_replays_to_the_same_state() {
+        let mut game = testing::game(&[]);
+        let scripts = testing::scripts();
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        game.state.players[0].position = 5;
+        game.start_recording();
+
+        // 출발점을 지나며 월급을 받은 뒤 없는 칸으로 가려다 실패
+        let warp = r#"#{ type: "WarpToPosition", position: 99 }"#;
+        assert!(matches!(game.run_turn_script(warp, DicePair(1, 2), &scripts.cycle), Err(GameError::InvalidPosition(_))));
+        assert_eq!(game.state.players[0].money, 1_000_000);
+
+        game.run_turn_script(&scripts.action, DicePair(1, 2), &scripts.cycle).unwrap();
+        assert_eq!(game.state.players[0].money, 1_200_000);
+        let replayed = GameEngine::replay(&game.replay_document()).unwrap();
+        assert_eq!(replayed.get_state_hash(), game.get_state_hash());
+    }
+}
@@ -0,0 +1,237 @@
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+
+use crate::{GameError, GameRng, GameSnapshot};
+
+/// 현재 세이브 파일 스키마 버전
+///
+/// 1: 봉투 없이 `GameSnapshot`을 그대로 직렬화한 형식 (대출과 소유 정보가 튜플)
+/// 2: 봉투 도입, `Loan`/`Ownership` 구조체로 전환
+/// 3: 게임 상태에 시드 기반 난수 생성기(`GameRng`) 추가
+pub const SCHEMA_VERSION: u32 = 3;
+
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// `MIGRATIONS[i]`는 스키마 버전 `i + 1`의 스냅샷을 `i + 2`로 올린다
+const MIGRATIONS: [Migration; (SCHEMA_VERSION - 1) as usize] = [
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveFile {
+    pub schema_version: u32,
+    pub engine_version: String,
+    pub ruleset_hash: String,
+    pub snapshot: Value,
+}
+
+#[derive(Serialize)]
+struct Ruleset<'a> {
+    board: &'a Value,
+    chance_cards_inventory: &'a Value,
+    consts: &'a Value,
+    salary: &'a Value,
+    building_cost: &'a Value,
+}
+
+impl SaveFile {
+    pub(crate) fn new(snapshot: &GameSnapshot) -> Self {
+        let snapshot = serde_json::to_value(snapshot).unwrap();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            engine_version: env!("CARGO_PKG_VERSION").into(),
+            ruleset_hash: ruleset_hash(&snapshot),
+            snapshot,
+        }
+    }
+
+    /// 세이브 파일을 읽어 현재 스키마까지 마이그레이션한다
+    ///
+    /// 봉투가 없는 JSON은 스키마 버전 1의 스냅샷으로 간주한다.
+    pub(crate) fn parse(json: &str) -> Result<Self, GameError> {
+        let value: Value = serde_json::from_str(json).map_err(|e| GameError::InvalidSave(e.to_string()))?;
+        let mut save = if value.get("schema_version").is_some() {
+            serde_json::from_value::<SaveFile>(value).map_err(|e| GameError::InvalidSave(e.to_string()))?
+        } else {
+            SaveFile {
+                schema_version: 1,
+                engine_version: String::new(),
+                ruleset_hash: ruleset_hash(&value),
+                snapshot: value,
+            }
+        };
+        save.migrate().map_err(GameError::InvalidSave)?;
+        Ok(save)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        if self.schema_version == 0 || self.schema_version > SCHEMA_VERSION {
+            return Err(format!("Unsupported save schema version {} (supported: 1..={})", self.schema_version, SCHEMA_VERSION));
+        }
+        for migration in &MIGRATIONS[(self.schema_version - 1) as usize..] {
+            migration(&mut self.snapshot)?;
+            self.schema_version += 1;
+        }
+        Ok(())
+    }
+
+    /// 이미 읽은 세이브 파일을 현재 스키마까지 마이그레이션한 뒤 스냅샷으로 변환
+    pub(crate) fn into_current_snapshot(mut self) -> Result<GameSnapshot, GameError> {
+        self.migrate().map_err(GameError::InvalidSave)?;
+        self.into_snapshot()
+    }
+
+    pub(crate) fn into_snapshot(self) -> Result<GameSnapshot, GameError> {
+        serde_json::from_value(self.snapshot).map_err(|e| GameError::InvalidSave(e.to_string()))
+    }
+}
+
+/// 보드, 찬스 카드, 상수, 월급, 건설비로부터 계산한 룰셋 식별용 해시
+pub(crate) fn ruleset_hash(snapshot: &Value) -> String {
+    let state = &snapshot["state"];
+    let ruleset = Ruleset {
+        board: &state["board"],
+        chance_cards_inventory: &state["chance_cards_inventory"],
+        consts: &state["consts"],
+        salary: &snapshot["salary"],
+        building_cost: &snapshot["building_cost"],
+    };
+    let value = serde_json::to_value(ruleset).unwrap_or(Value::Null);
+    format!("{:016x}", fnv1a(canonical_json(&value).as_bytes()))
+}
+
+/// 스냅샷 전체로부터 계산한 상태 식별용 해시
+pub(crate) fn state_hash(snapshot: &GameSnapshot) -> String {
+    let value = serde_json::to_value(snapshot).unwrap_or(Value::Null);
+    format!("{:016x}", fnv1a(canonical_json(&value).as_bytes()))
+}
+
+/// 키를 정렬해 직렬화하여, `HashMap`의 순회 순서와 무관하게 같은 값은 같은 문자열이 되도록 함
+pub(crate) fn canonical_json(value: &Value) -> String {
+    fn sorted(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                Value::Object(entries.into_iter().map(|(k, v)| (k.clone(), sorted(v))).collect::<Map<_, _>>())
+            },
+            Value::Array(items) => Value::Array(items.iter().map(sorted).collect()),
+            _ => value.clone()
+        }
+    }
+    sorted(value).to_string()
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325_u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn migrate_v1_to_v2(snapshot: &mut Value) -> Result<(), String> {
+    let state = snapshot.get_mut("state").and_then(Value::as_object_mut).ok_or("v1 save has no 'state'")?;
+
+    // remaining_loans: [(id, amount, remaining_turns)] -> [Loan]
+    if let Some(Value::Array(players)) = state.get_mut("players") {
+        for player in players.iter_mut() {
+            if let Some(Value::Array(loans)) = player.get_mut("remaining_loans") {
+                for loan in loans.iter_mut() {
+                    if let Value::Array(fields) = loan {
+                        let [id, amount, remaining_turns] = <[Value; 3]>::try_from(fields.clone()).map_err(|_| "v1 loan must be a 3-tuple")?;
+                        *loan = serde_json::json!({ "id": id, "amount": amount, "remaining_turns": remaining_turns });
+                    }
+                }
+            }
+        }
+    }
+
+    // properties: {name: (owner_id, buildings)} -> {name: Ownership}
+    if let Some(Value::Object(properties)) = state.get_mut("properties") {
+        for ownership in properties.values_mut() {
+            if let Value::Array(fields) = ownership {
+                let [owner_id, buildings] = <[Value; 2]>::try_from(fields.clone()).map_err(|_| "v1 property must be a 2-tuple")?;
+                *ownership = serde_json::json!({ "owner_id": owner_id, "buildings": buildings });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn migrate_v2_to_v3(snapshot: &mut Value) -> Result<(), String> {
+    // 시드가 없던 세이브는 스냅샷 내용으로부터 시드를 만들어, 같은 세이브는 항상 같은 난수열을 갖도록 함
+    let seed = fnv1a(canonical_json(snapshot).as_bytes());
+    let state = snapshot.get_mut("state").and_then(Value::as_object_mut).ok_or("v2 save has no 'state'")?;
+    state.insert("rng".into(), serde_json::to_value(GameRng::new(seed)).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{canonical_json, fnv1a, migrate_v1_to_v2, state_hash, SaveFile, SCHEMA_VERSION};
+    use crate::{testing, GameEngine, GameRng, GameSnapshot, Ownership};
+
+    /// 대출 하나와 부동산 하나가 있는 게임의 현재 스냅샷
+    fn current_snapshot() -> Value {
+        let mut game = testing::game(&[]);
+        game.borrow_money(1, 100_000).unwrap();
+        game.state.properties.insert("Seoul".into(), Ownership { owner_id: 1, buildings: 1, mortgaged: false });
+        serde_json::to_value(game.snapshot()).unwrap()
+    }
+
+    /// 현재 스냅샷을 스키마 버전 2의 형식으로 (난수 생성기 없음)
+    fn v2_snapshot() -> Value {
+        let mut snapshot = current_snapshot();
+        snapshot["state"].as_object_mut().unwrap().remove("rng");
+        snapshot
+    }
+
+    /// 현재 스냅샷을 스키마 버전 1의 형식으로 (대출과 소유 정보가 튜플)
+    fn v1_snapshot() -> Value {
+        let mut snapshot = v2_snapshot();
+        let state = &mut snapshot["state"];
+        state["players"][0]["remaining_loans"] = json!([[0, 100_000, 4]]);
+        state["properties"] = json!({ "Seoul": [1, 1] });
+        snapshot
+    }
+
+    /// 마이그레이션한 스냅샷에 들어가야 할 상태 (`seed`로 만든 난수 생성기)
+    fn expected_snapshot(seed: u64) -> GameSnapshot {
+        let mut snapshot = current_snapshot();
+        snapshot["state"]["rng"] = serde_json::to_value(GameRng::new(seed)).unwrap();
+        serde_json::from_value(snapshot).unwrap()
+    }
+
+    #[test]
+    fn v1_snapshot_without_envelope_is_migrated() {
+        let v1 = v1_snapshot();
+        let save = SaveFile::parse(&v1.to_string()).unwrap();
+        assert_eq!(save.schema_version, SCHEMA_VERSION);
+        assert_eq!(save.ruleset_hash, GameEngine::from_snapshot(&current_snapshot().to_string()).unwrap().get_ruleset_hash());
+        let state = &save.snapshot["state"];
+        assert_eq!(state["players"][0]["remaining_loans"], json!([{ "id": 0, "amount": 100_000, "remaining_turns": 4 }]));
+        assert_eq!(state["properties"]["Seoul"], json!({ "owner_id": 1, "buildings": 1 }));
+
+        let mut v2 = v1.clone();
+        migrate_v1_to_v2(&mut v2).unwrap();
+        let seed = fnv1a(canonical_json(&v2).as_bytes());
+        let game = GameEngine::from_snapshot(&v1.to_string()).unwrap();
+        assert_eq!(game.state.rng, GameRng::new(seed));
+        assert_eq!(state_hash(&game.snapshot()), state_hash(&expected_snapshot(seed)));
+    }
+
+    #[test]
+    fn v2_envelope_is_migrated_and_keeps_its_ruleset_hash() {
+        let v2 = v2_snapshot();
+        let envelope = json!({ "schema_version": 2, "engine_version": "0.0.1", "ruleset_hash": "0123456789abcdef", "snapshot": v2 });
+        let save = SaveFile::parse(&envelope.to_string()).unwrap();
+        assert_eq!((save.schema_version, save.engine_version.as_str(), save.ruleset_hash.as_str()), (SCHEMA_VERSION, "0.0.1", "0123456789abcdef"));
+
+        let seed = fnv1a(canonical_json(&v2).as_bytes());
+        let game = GameEngine::from_snapshot(&envelope.to_string()).unwrap();
+        assert_eq!(game.state.rng, GameRng::new(seed));
+        assert_eq!(state_hash(&game.snapshot()), state_hash(&expected_snapshot(seed)));
+    }
+}
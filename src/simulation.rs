@@ -0,0 +1,510 @@
+use std::{collections::{BTreeMap, HashMap}, fmt::Write, thread};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{BuiltinStrategy, ChanceCard, GameEngine, GameError, GameEvent, PendingDecision, Tile};
+
+/// 턴(`action`), 한 바퀴(`cycle`), 찬스 카드(`chance`) 스크립트
+#[derive(Clone, Debug, Default)]
+pub struct Scripts {
+    pub action: String,
+    pub cycle: String,
+    pub chance: String,
+}
+
+/// 시뮬레이션에서 한 자리를 맡는 봇
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeatStrategy {
+    Builtin(BuiltinStrategy),
+    /// Rhai 전략 스크립트 (`name`은 보고서에 쓰는 이름)
+    Script { name: String, source: String },
+}
+
+impl SeatStrategy {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Builtin(strategy) => strategy.name(),
+            Self::Script { name, .. } => name,
+        }
+    }
+
+    /// `player_id`를 이 전략의 봇이 조종하게 함 (`seed`는 무작위 전략만 사용)
+    pub fn assign(&self, game: &mut GameEngine, player_id: u32, seed: u64) -> Result<(), GameError> {
+        match self {
+            Self::Builtin(strategy) => game.set_builtin_bot(player_id, *strategy, seed),
+            Self::Script { source, .. } => game.set_script_bot(player_id, source),
+        }
+    }
+}
+
+/// 시뮬레이션할 룰셋과 게임 수
+///
+/// `i`번째 게임은 `seed + i`로 시작하므로 같은 설정이면 같은 보고서가 나온다.
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    pub board: Vec<Tile>,
+    pub chance_cards_inventory: HashMap<String, ChanceCard>,
+    pub consts: HashMap<String, u32>,
+    pub scripts: Scripts,
+    pub players_count: usize,
+    pub initial_money: i64,
+    pub salary: i64,
+    pub building_cost: i64,
+    /// 자리별 봇 전략, `i`번째 자리는 `strategies[i % len]` (비어 있으면 모두 `Greedy`)
+    pub strategies: Vec<SeatStrategy>,
+    pub games: u32,
+    pub seed: u64,
+    /// 이 턴 수가 지나도 끝나지 않은 게임은 끝나지 않은 것으로 셈
+    pub max_turns: u32,
+    /// 게임을 나눠 돌릴 스레드 수 (0이면 사용 가능한 코어 수)
+    pub threads: usize,
+}
+
+impl SimulationConfig {
+    pub fn new(board: Vec<Tile>, chance_cards_inventory: HashMap<String, ChanceCard>, consts: HashMap<String, u32>, scripts: Scripts) -> Self {
+        SimulationConfig {
+            board, chance_cards_inventory, consts, scripts,
+            players_count: 2,
+            initial_money: 3_000_000,
+            salary: 200_000,
+            building_cost: 100_000,
+            strategies: vec![SeatStrategy::Builtin(BuiltinStrategy::Greedy)],
+            games: 1000,
+            seed: 0,
+            max_turns: 1000,
+            threads: 0,
+        }
+    }
+
+    /// `player_id` 자리의 봇 전략
+    pub fn strategy_of(&self, player_id: u32) -> &SeatStrategy {
+        const GREEDY: SeatStrategy = SeatStrategy::Builtin(BuiltinStrategy::Greedy);
+        match self.strategies.len() {
+            0 => &GREEDY,
+            len => &self.strategies[(player_id as usize - 1) % len],
+        }
+    }
+
+    /// `GameEngine::new`와 같은 JSON 룰셋으로 설정을 만듦
+    pub fn from_json(board_json: &str, chance_cards_json: &str, consts_json: &str, scripts: Scripts) -> Result<Self, GameError> {
+        let board = serde_json::from_str(board_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        let chance_cards_inventory = serde_json::from_str(chance_cards_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        let consts = serde_json::from_str(consts_json).map_err(|e| GameError::InvalidJson(e.to_string()))?;
+        Ok(Self::new(board, chance_cards_inventory, consts, scripts))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TurnStats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// 자리(플레이어 id)별 통계, 비율은 끝난 게임 수에 대한 것
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SeatStats {
+    pub player_id: u32,
+    /// 내장 전략의 이름이나 스크립트의 이름
+    pub strategy: String,
+    pub wins: u32,
+    pub win_rate: f64,
+    pub bankruptcies: u32,
+    pub bankruptcy_rate: f64,
+    pub mean_final_net_worth: f64,
+}
+
+/// 이동(`Moved`), 순간 이동(`Warped`), 여행(`Traveled`)으로 도착한 횟수
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TileStats {
+    pub position: u32,
+    pub tile_name: String,
+    pub landings: u64,
+    /// 모든 도착 중 이 타일의 비율
+    pub landing_rate: f64,
+}
+
+/// 카드를 뽑은 플레이어의 순자산이 `check_chance_card` 전후로 바뀐 정도
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChanceCardStats {
+    pub card_id: String,
+    pub draws: u64,
+    pub mean_net_worth_change: f64,
+    pub min_net_worth_change: i64,
+    pub max_net_worth_change: i64,
+}
+
+/// 스크립트 오류 등으로 중간에 멈춘 게임 (`seed`로 다시 돌려볼 수 있음)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FailedGame {
+    pub seed: u64,
+    pub turn: u32,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SimulationReport {
+    pub games: u32,
+    /// 종료 조건으로 끝난 게임 수
+    pub finished: u32,
+    /// `max_turns`가 지나도 끝나지 않은 게임 수
+    pub unfinished: u32,
+    pub failed: Vec<FailedGame>,
+    /// 끝난 게임의 길이 (턴)
+    pub turns: TurnStats,
+    /// `EndReason` 종류별 게임 수
+    pub end_reasons: BTreeMap<String, u32>,
+    pub seats: Vec<SeatStats>,
+    /// 끝난 게임 한 판당 평균 파산 수
+    pub bankruptcies_per_game: f64,
+    /// 끝난 게임의 마지막 `government_income` 평균
+    pub mean_government_income: f64,
+    pub tiles: Vec<TileStats>,
+    pub chance_cards: Vec<ChanceCardStats>,
+}
+
+/// 한 게임의 결과
+struct GameRecord {
+    seed: u64,
+    turns: u32,
+    end_reason: Option<String>,
+    winner: Option<u32>,
+    bankrupt: Vec<u32>,
+    net_worths: Vec<(u32, i64)>,
+    government_income: i64,
+    landings: Vec<u32>,
+    /// (카드 id, 순자산 변화)
+    chance_cards: Vec<(String, i64)>,
+    error: Option<String>,
+}
+
+/// 게임 한 판에서 진행이 없다고 보는 한 턴당 명령 수
+const MAX_STEPS_PER_TURN: u32 = 200;
+
+fn end_reason_name(game: &GameEngine) -> Option<String> {
+    let value = serde_json::to_value(game.end_reason()?).ok()?;
+    value.get("type").and_then(|name| name.as_str()).map(String::from)
+}
+
+fn play_game(config: &SimulationConfig, seed: u64) -> Result<GameRecord, GameError> {
+    let mut game = GameEngine::with_rules(
+        config.board.clone(), config.chance_cards_inventory.clone(), config.consts.clone(),
+        config.players_count, config.initial_money, config.salary, config.building_cost, Some(seed),
+    )?;
+    for player_id in 1..=config.players_count as u32 {
+        // 무작위 전략은 게임과 자리마다 다른 시드를 씀
+        config.strategy_of(player_id).assign(&mut game, player_id, seed.wrapping_mul(31).wrapping_add(player_id as u64))?;
+    }
+
+    let mut chance_cards = Vec::new();
+    let mut error = None;
+    let mut steps = 0;
+    while game.end_reason().is_none() && game.state().turn_count() < config.max_turns {
+        steps += 1;
+        if steps > (config.max_turns + 1) * MAX_STEPS_PER_TURN {
+            error = Some("The game made no progress.".to_string());
+            break;
+        }
+        let drawn = match game.pending_decision() {
+            PendingDecision::PendingCheckChanceCardResponse { card_id, .. } => Some((card_id, game.net_worth(game.state().current_player()))),
+            _ => None,
+        };
+        let player_id = game.state().current_player().id;
+        match game.step_bot(&config.scripts) {
+            Ok(Some(_)) => {},
+            Ok(None) => {
+                error = Some(format!("No bot can act while {:?}.", game.get_situation()));
+                break;
+            },
+            Err(e) => {
+                error = Some(format!("[{}] {}", e.code(), e));
+                break;
+            },
+        }
+        if let Some((card_id, before)) = drawn {
+            let player = game.state().players().iter().find(|player| player.id == player_id).unwrap();
+            chance_cards.push((card_id, game.net_worth(player) - before));
+        }
+    }
+
+    let landings = game.events_since(0).iter().filter_map(|event| match event {
+        GameEvent::Moved { to, .. } => Some(*to),
+        GameEvent::Warped { position, .. } | GameEvent::Traveled { position, .. } => Some(*position),
+        _ => None,
+    }).collect();
+    let state = game.state();
+    Ok(GameRecord {
+        seed,
+        turns: state.turn_count(),
+        end_reason: end_reason_name(&game),
+        winner: game.get_winner(),
+        bankrupt: state.players().iter().filter(|player| player.bankrupt).map(|player| player.id).collect(),
+        net_worths: state.players().iter().map(|player| (player.id, game.net_worth(player))).collect(),
+        government_income: state.government_income(),
+        landings,
+        chance_cards,
+        error,
+    })
+}
+
+fn mean(sum: f64, count: usize) -> f64 {
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+impl SimulationReport {
+    fn from_records(config: &SimulationConfig, records: &[GameRecord]) -> Self {
+        let failed = records.iter().filter_map(|record| record.error.as_ref().map(|error| FailedGame { seed: record.seed, turn: record.turns, error: error.clone() })).collect::<Vec<_>>();
+        let finished = records.iter().filter(|record| record.error.is_none() && record.end_reason.is_some()).collect::<Vec<_>>();
+        let unfinished = records.iter().filter(|record| record.error.is_none() && record.end_reason.is_none()).count() as u32;
+
+        let mut turns = finished.iter().map(|record| record.turns).collect::<Vec<_>>();
+        turns.sort_unstable();
+        let median = match turns.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (turns[len / 2 - 1] + turns[len / 2]) as f64 / 2.0,
+            len => turns[len / 2] as f64,
+        };
+        let turns = TurnStats {
+            mean: mean(turns.iter().map(|&turn| turn as f64).sum(), turns.len()),
+            median,
+            min: turns.first().copied().unwrap_or(0),
+            max: turns.last().copied().unwrap_or(0),
+        };
+
+        let mut end_reasons = BTreeMap::new();
+        for reason in finished.iter().filter_map(|record| record.end_reason.clone()) {
+            *end_reasons.entry(reason).or_insert(0) += 1;
+        }
+
+        let seats = (1..=config.players_count as u32).map(|player_id| {
+            let wins = finished.iter().filter(|record| record.winner == Some(player_id)).count() as u32;
+            let bankruptcies = finished.iter().filter(|record| record.bankrupt.contains(&player_id)).count() as u32;
+            let net_worth = finished.iter().flat_map(|record| record.net_worths.iter()).filter(|(pid, _)| *pid == player_id).map(|(_, net_worth)| *net_worth as f64).sum();
+            SeatStats {
+                player_id,
+                strategy: config.strategy_of(player_id).name().into(),
+                wins,
+                win_rate: mean(wins as f64, finished.len()),
+                bankruptcies,
+                bankruptcy_rate: mean(bankruptcies as f64, finished.len()),
+                mean_final_net_worth: mean(net_worth, finished.len()),
+            }
+        }).collect();
+
+        let mut landings = vec![0_u64; config.board.len()];
+        for &position in records.iter().flat_map(|record| record.landings.iter()) {
+            if let Some(count) = landings.get_mut(position as usize) {
+                *count += 1;
+            }
+        }
+        let total_landings = landings.iter().sum::<u64>();
+        let tiles = config.board.iter().zip(landings).enumerate().map(|(position, (tile, landings))| TileStats {
+            position: position as u32,
+            tile_name: tile.name.clone(),
+            landings,
+            landing_rate: mean(landings as f64, total_landings as usize),
+        }).collect();
+
+        let mut by_card = BTreeMap::<&str, Vec<i64>>::new();
+        for (card_id, change) in records.iter().flat_map(|record| record.chance_cards.iter()) {
+            by_card.entry(card_id.as_str()).or_default().push(*change);
+        }
+        let chance_cards = by_card.into_iter().map(|(card_id, changes)| ChanceCardStats {
+            card_id: card_id.into(),
+            draws: changes.len() as u64,
+            mean_net_worth_change: mean(changes.iter().map(|&change| change as f64).sum(), changes.len()),
+            min_net_worth_change: changes.iter().copied().min().unwrap_or(0),
+            max_net_worth_change: changes.iter().copied().max().unwrap_or(0),
+        }).collect();
+
+        SimulationReport {
+            games: records.len() as u32,
+            finished: finished.len() as u32,
+            unfinished,
+            failed,
+            turns,
+            end_reasons,
+            seats,
+            bankruptcies_per_game: mean(finished.iter().map(|record| record.bankrupt.len() as f64).sum(), finished.len()),
+            mean_government_income: mean(finished.iter().map(|record| record.government_income as f64).sum(), finished.len()),
+            tiles,
+            chance_cards,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// `table,key,metric,value` 열의 긴 형식 CSV
+    ///
+    /// `table`은 `summary`, `end_reason`, `seat`, `tile`, `chance_card`, `failed` 중 하나이고,
+    /// `key`는 자리의 플레이어 id, 타일 위치, 카드 id, 멈춘 게임의 시드다.
+    pub fn to_csv(&self) -> String {
+        fn quote(field: &str) -> String {
+            if field.contains([',', '"', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.into()
+            }
+        }
+        let mut csv = String::from("table,key,metric,value\n");
+        let mut row = |table: &str, key: &str, metric: &str, value: &dyn std::fmt::Display| {
+            writeln!(csv, "{},{},{},{}", table, quote(key), metric, quote(&value.to_string())).unwrap();
+        };
+
+        row("summary", "", "games", &self.games);
+        row("summary", "", "finished", &self.finished);
+        row("summary", "", "unfinished", &self.unfinished);
+        row("summary", "", "failed", &self.failed.len());
+        row("summary", "", "mean_turns", &self.turns.mean);
+        row("summary", "", "median_turns", &self.turns.median);
+        row("summary", "", "min_turns", &self.turns.min);
+        row("summary", "", "max_turns", &self.turns.max);
+        row("summary", "", "bankruptcies_per_game", &self.bankruptcies_per_game);
+        row("summary", "", "mean_government_income", &self.mean_government_income);
+        for (reason, count) in self.end_reasons.iter() {
+            row("end_reason", reason, "games", count);
+        }
+        for seat in self.seats.iter() {
+            let key = seat.player_id.to_string();
+            row("seat", &key, "strategy", &seat.strategy);
+            row("seat", &key, "wins", &seat.wins);
+            row("seat", &key, "win_rate", &seat.win_rate);
+            row("seat", &key, "bankruptcies", &seat.bankruptcies);
+            row("seat", &key, "bankruptcy_rate", &seat.bankruptcy_rate);
+            row("seat", &key, "mean_final_net_worth", &seat.mean_final_net_worth);
+        }
+        for tile in self.tiles.iter() {
+            let key = tile.position.to_string();
+            row("tile", &key, "tile_name", &tile.tile_name);
+            row("tile", &key, "landings", &tile.landings);
+            row("tile", &key, "landing_rate", &tile.landing_rate);
+        }
+        for card in self.chance_cards.iter() {
+            row("chance_card", &card.card_id, "draws", &card.draws);
+            row("chance_card", &card.card_id, "mean_net_worth_change", &card.mean_net_worth_change);
+            row("chance_card", &card.card_id, "min_net_worth_change", &card.min_net_worth_change);
+            row("chance_card", &card.card_id, "max_net_worth_change", &card.max_net_worth_change);
+        }
+        for failed in self.failed.iter() {
+            let key = failed.seed.to_string();
+            row("failed", &key, "turn", &failed.turn);
+            row("failed", &key, "error", &failed.error);
+        }
+        csv
+    }
+}
+
+/// 같은 룰셋으로 게임을 `config.games`판 돌리고 통계를 모음
+///
+/// 룰셋 자체가 잘못되었다면 오류를 내고, 게임 중의 스크립트 오류는 `SimulationReport.failed`에 남긴다.
+pub fn simulate(config: &SimulationConfig) -> Result<SimulationReport, GameError> {
+    let threads = match config.threads {
+        0 => thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1),
+        threads => threads,
+    }.clamp(1, config.games.max(1) as usize);
+    let seeds = (0..config.games as u64).map(|i| config.seed.wrapping_add(i)).collect::<Vec<_>>();
+
+    let records = if threads == 1 {
+        seeds.iter().map(|&seed| play_game(config, seed)).collect::<Result<Vec<_>, _>>()?
+    } else {
+        // 스크립트 엔진은 스레드 사이에 옮길 수 없으므로 게임마다 스레드 안에서 만듦
+        let chunk = seeds.len().div_ceil(threads);
+        thread::scope(|scope| {
+            let handles = seeds.chunks(chunk).map(|seeds| scope.spawn(move || {
+                seeds.iter().map(|&seed| play_game(config, seed)).collect::<Result<Vec<_>, _>>()
+            })).collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Result<Vec<_>, _>>()
+        })?.into_iter().flatten().collect()
+    };
+    Ok(SimulationReport::from_records(config, &records))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{testing, BuiltinStrategy};
+    use super::*;
+
+    fn config(games: u32, threads: usize) -> SimulationConfig {
+        let consts = [("CYCLE_LIMIT".to_string(), 2)].into();
+        let mut config = SimulationConfig::new(testing::board(), HashMap::new(), consts, testing::scripts());
+        config.strategies = vec![SeatStrategy::Builtin(BuiltinStrategy::Greedy), SeatStrategy::Builtin(BuiltinStrategy::Random)];
+        config.initial_money = 1_000_000;
+        config.games = games;
+        config.seed = 42;
+        config.max_turns = 200;
+        config.threads = threads;
+        config
+    }
+
+    fn record(seed: u64, turns: u32, end_reason: Option<&str>, winner: Option<u32>, bankrupt: Vec<u32>) -> GameRecord {
+        GameRecord {
+            seed, turns,
+            end_reason: end_reason.map(String::from),
+            winner, bankrupt,
+            net_worths: vec![(1, 1_000), (2, 3_000)],
+            government_income: 500,
+            landings: vec![1, 1, 2],
+            chance_cards: vec![("lotto".into(), 100), ("lotto".into(), -300)],
+            error: None,
+        }
+    }
+
+    #[test]
+    fn seeded_simulation_accounts_for_every_game() {
+        let report = simulate(&config(6, 1)).unwrap();
+        assert_eq!(report.games, 6);
+        assert_eq!(report.finished + report.unfinished + report.failed.len() as u32, 6);
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        assert!(report.finished > 0);
+        assert_eq!(report.seats.iter().map(|seat| seat.wins).sum::<u32>(), report.finished);
+        assert_eq!(report.end_reasons.values().sum::<u32>(), report.finished);
+        assert_eq!(report.seats.iter().map(|seat| seat.strategy.as_str()).collect::<Vec<_>>(), ["greedy", "random"]);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_report_on_any_number_of_threads() {
+        let report = simulate(&config(6, 1)).unwrap();
+        let again = simulate(&config(6, 3)).unwrap();
+        assert_eq!(report, again);
+        assert_eq!(report.to_json(), again.to_json());
+        assert_eq!(report.to_csv(), again.to_csv());
+    }
+
+    #[test]
+    fn report_sums_up_the_records() {
+        let mut failed = record(3, 7, None, None, vec![]);
+        failed.error = Some("[ScriptError] boom, again".into());
+        let records = [
+            record(0, 10, Some("CycleLimit"), Some(2), vec![]),
+            record(1, 20, Some("LastPlayerStanding"), Some(2), vec![1]),
+            record(2, 30, None, None, vec![]),
+            failed,
+        ];
+        let report = SimulationReport::from_records(&config(4, 1), &records);
+
+        assert_eq!((report.games, report.finished, report.unfinished), (4, 2, 1));
+        assert_eq!(report.failed, [FailedGame { seed: 3, turn: 7, error: "[ScriptError] boom, again".into() }]);
+        assert_eq!(report.turns, TurnStats { mean: 15.0, median: 15.0, min: 10, max: 20 });
+        assert_eq!(report.end_reasons, [("CycleLimit".to_string(), 1), ("LastPlayerStanding".to_string(), 1)].into());
+        assert_eq!(report.seats.iter().map(|seat| (seat.wins, seat.bankruptcies)).collect::<Vec<_>>(), [(0, 1), (2, 0)]);
+        assert_eq!(report.seats[1].win_rate, 1.0);
+        assert_eq!(report.bankruptcies_per_game, 0.5);
+        assert_eq!(report.tiles[1].landings, 8);
+        assert_eq!(report.chance_cards[0].draws, 8);
+        assert_eq!(report.chance_cards[0].mean_net_worth_change, -100.0);
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("table,key,metric,value\nsummary,,games,4\nsummary,,finished,2\n"));
+        assert!(csv.contains("end_reason,CycleLimit,games,1\n"));
+        assert!(csv.contains("seat,2,wins,2\n"));
+        assert!(csv.contains("failed,3,error,\"[ScriptError] boom, again\"\n"));
+
+        let json = serde_json::from_str::<SimulationReport>(&report.to_json()).unwrap();
+        assert_eq!(json, report);
+    }
+}
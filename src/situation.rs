@@ -0,0 +1,155 @@
+use serde::{Serialize, Deserialize};
+
+use crate::GameSituation;
+
+/// 호스트가 엔진에 내릴 수 있는 명령의 종류
+///
+/// 이름은 해당 명령을 수행하는 `GameEngine`의 메서드 이름을 따른다.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    RollDice,
+    RunTurnScript,
+    AcceptPurchase,
+    DeclinePurchase,
+    UseTicket,
+    LuckTest,
+    StopLuckTest,
+    TryToJailbreakByDices,
+    TryToJailbreakByMoney,
+    GiveUpJailbreak,
+    GetRandomChanceCard,
+    CheckChanceCard,
+    BorrowMoney,
+    RepayLoan,
+    SellBuilding,
+    MortgageProperty,
+    RedeemMortgage,
+    ResolveFinancialCrisis,
+    DeclareBankruptcy,
+    ProposeTrade,
+    AcceptTrade,
+    RejectTrade,
+    CounterTrade,
+    PlaceBid,
+    PassBid,
+    CloseAuction,
+    EndTurn,
+}
+
+/// 대출, 상환, 저당 해제는 게임이 진행 중이고 스크립트를 실행하고 있지 않다면 언제든 가능
+const FINANCE: [Command; 3] = [Command::BorrowMoney, Command::RepayLoan, Command::RedeemMortgage];
+
+impl Command {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::RollDice => "roll_dice",
+            Self::RunTurnScript => "run_turn_script",
+            Self::AcceptPurchase => "accept_purchase",
+            Self::DeclinePurchase => "decline_purchase",
+            Self::UseTicket => "use_ticket",
+            Self::LuckTest => "luck_test",
+            Self::StopLuckTest => "stop_luck_test",
+            Self::TryToJailbreakByDices => "try_to_jailbreak_by_dices",
+            Self::TryToJailbreakByMoney => "try_to_jailbreak_by_money",
+            Self::GiveUpJailbreak => "give_up_jailbreak",
+            Self::GetRandomChanceCard => "get_random_chance_card",
+            Self::CheckChanceCard => "check_chance_card",
+            Self::BorrowMoney => "borrow_money",
+            Self::RepayLoan => "repay_loan",
+            Self::SellBuilding => "sell_building",
+            Self::MortgageProperty => "mortgage_property",
+            Self::RedeemMortgage => "redeem_mortgage",
+            Self::ResolveFinancialCrisis => "resolve_financial_crisis",
+            Self::DeclareBankruptcy => "declare_bankruptcy",
+            Self::ProposeTrade => "propose_trade",
+            Self::AcceptTrade => "accept_trade",
+            Self::RejectTrade => "reject_trade",
+            Self::CounterTrade => "counter_trade",
+            Self::PlaceBid => "place_bid",
+            Self::PassBid => "pass_bid",
+            Self::CloseAuction => "close_auction",
+            Self::EndTurn => "end_turn",
+        }
+    }
+}
+
+impl GameSituation {
+    /// 상태 전이표: 각 상황에서 허용되는 명령
+    ///
+    /// 움직이거나 주사위로 탈옥하려면 먼저 `RollDice`로 엔진이 주사위를 굴려야 하며,
+    /// 굴린 뒤에는 그 주사위를 쓰기 전까지 다시 굴릴 수 없다.
+    pub fn legal_commands(self) -> Vec<Command> {
+        let commands: &[Command] = match self {
+            Self::InAction | Self::EndGame => &[],
+            // 거래는 주사위를 굴리기 전이나 턴을 끝내기 전에만 제안할 수 있음
+            Self::PendingRollResponse => &[Command::RollDice, Command::ProposeTrade],
+            Self::PendingMoveResponse => &[Command::RunTurnScript],
+            Self::PendingBuyResponse => &[Command::AcceptPurchase, Command::DeclinePurchase],
+            Self::PendingFinancialCrisisResponse => &[Command::SellBuilding, Command::MortgageProperty, Command::ResolveFinancialCrisis, Command::DeclareBankruptcy],
+            Self::PendingLuckTestResponse => &[Command::LuckTest, Command::StopLuckTest],
+            Self::PendingUseTicketResponse => &[Command::UseTicket],
+            Self::PendingTryToJailbreakResponse => &[Command::RollDice, Command::TryToJailbreakByMoney, Command::GiveUpJailbreak],
+            Self::PendingJailbreakByDicesResponse => &[Command::TryToJailbreakByDices],
+            Self::PendingGetRandomChanceCardResponse => &[Command::GetRandomChanceCard],
+            Self::PendingCheckChanceCardResponse => &[Command::CheckChanceCard],
+            Self::PendingTradeResponse => &[Command::AcceptTrade, Command::RejectTrade, Command::CounterTrade],
+            Self::PendingAuction => &[Command::PlaceBid, Command::PassBid, Command::CloseAuction],
+            Self::EndTurn => &[Command::EndTurn, Command::ProposeTrade],
+        };
+        let finance: &[Command] = match self {
+            Self::InAction | Self::EndGame => &[],
+            _ => &FINANCE,
+        };
+        commands.iter().chain(finance).copied().collect()
+    }
+
+    /// 주사위 출처가 호스트의 주사위를 받을 때(`DiceSource::accepts_host_dice`) 굴리지 않고 쓸 수 있는 명령
+    pub fn host_dice_commands(self) -> &'static [Command] {
+        match self {
+            Self::PendingRollResponse => &[Command::RunTurnScript],
+            Self::PendingTryToJailbreakResponse => &[Command::TryToJailbreakByDices],
+            _ => &[],
+        }
+    }
+
+    pub fn allows(self, command: Command) -> bool {
+        self.legal_commands().contains(&command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, Command, DicePair, GameError, GameSituation, ScriptedDice};
+
+    #[test]
+    fn seeded_dice_must_be_rolled_by_the_engine_and_used_once() {
+        let mut game = testing::game(&[]);
+        let scripts = testing::scripts();
+        assert!(!game.legal_commands().contains(&Command::RunTurnScript));
+        assert!(matches!(game.run_turn_script(&scripts.action, DicePair(1, 2), &scripts.cycle), Err(GameError::InvalidSituation { .. })));
+
+        let dices = game.roll_dice().unwrap();
+        assert_eq!(game.get_situation(), GameSituation::PendingMoveResponse);
+        assert!(matches!(game.roll_dice(), Err(GameError::InvalidSituation { .. })));
+        let other = DicePair(dices.0 % 6 + 1, dices.1);
+        assert_eq!(game.run_turn_script(&scripts.action, other, &scripts.cycle), Err(GameError::UnrolledDice { first: other.0, second: other.1 }));
+
+        // 스크립트가 실패해도 굴린 주사위는 남아 다시 시도할 수 있음
+        assert!(game.run_turn_script(r#"#{ type: "PayTax" }"#, dices, &scripts.cycle).is_err());
+        assert_eq!(game.get_situation(), GameSituation::PendingMoveResponse);
+        game.run_turn_script(&scripts.action, dices, &scripts.cycle).unwrap();
+        assert_eq!(game.state().current_player().position, (dices.0 + dices.1) as u32 % 6);
+        assert!(!game.legal_commands().contains(&Command::RunTurnScript));
+    }
+
+    #[test]
+    fn host_dice_are_accepted_only_when_the_dice_are_not_seeded() {
+        let mut game = testing::game(&[]);
+        let scripts = testing::scripts();
+        game.set_dice_source(Box::new(ScriptedDice::new([])));
+        assert!(game.legal_commands().contains(&Command::RunTurnScript));
+        game.run_turn_script(&scripts.action, DicePair(1, 2), &scripts.cycle).unwrap();
+        assert_eq!(game.state().current_player().position, 3);
+    }
+}
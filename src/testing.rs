@@ -0,0 +1,58 @@
+//! 테스트에서 쓰는 작은 룰셋
+
+use std::collections::HashMap;
+
+use crate::{GameEngine, Scripts, Tile};
+
+pub(crate) fn tile(name: &str, tile_type: &str, price: i64) -> Tile {
+    Tile {
+        name: name.into(),
+        tile_type: tile_type.into(),
+        price,
+        amount: 0,
+        is_coastal: false,
+        is_megacity: false,
+        rent: Vec::new(),
+        buildings: Vec::new(),
+    }
+}
+
+/// 출발점, 감옥, 부동산 네 곳으로 된 여섯 칸 보드
+pub(crate) fn board() -> Vec<Tile> {
+    vec![
+        tile("Start", "Start", 0),
+        tile("Seoul", "Property", 100_000),
+        tile("Busan", "Property", 200_000),
+        Tile { amount: 50_000, ..tile("Jail", "Jail", 0) },
+        tile("Incheon", "Property", 300_000),
+        tile("Daegu", "Property", 400_000),
+    ]
+}
+
+/// 빈 땅은 살지 묻고, 남의 땅이면 통행료를 내며, 그 밖의 타일에서는 아무 일도 없는 스크립트
+pub(crate) fn scripts() -> Scripts {
+    Scripts {
+        action: r#"
+            if tile.type != "Property" {
+                #{ type: "Log", message: "Nothing happens." }
+            } else if is_owned {
+                #{ type: "PayRent" }
+            } else {
+                #{ type: "PromptBuy", tile_name: tile.name, price: tile.price }
+            }
+        "#.into(),
+        cycle: r#"#{ new_government_income: government_income, remaining_salary: salary, basic_income: 0 }"#.into(),
+        chance: r#"#{ type: "NOP" }"#.into(),
+    }
+}
+
+/// 두 명이 1000000씩 가지고 시작하는 게임 (시드 7)
+pub(crate) fn game(consts: &[(&str, u32)]) -> GameEngine {
+    players_game(2, consts)
+}
+
+/// `players_count`명이 1000000씩 가지고 시작하는 게임 (시드 7)
+pub(crate) fn players_game(players_count: usize, consts: &[(&str, u32)]) -> GameEngine {
+    let consts = consts.iter().map(|&(key, value)| (key.to_string(), value)).collect();
+    GameEngine::with_rules(board(), HashMap::new(), consts, players_count, 1_000_000, 200_000, 100_000, Some(7)).unwrap()
+}
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Command, GameEngine, GameError, GameEvent, GameSituation, ReplayStep, TicketCount};
+
+/// 거래에서 한쪽이 내놓는 것
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TradeSide {
+    #[serde(default)]
+    pub money: i64,
+    /// 부동산 이름 (건물과 저당 상태도 함께 넘어감)
+    #[serde(default)]
+    pub properties: Vec<String>,
+    #[serde(default)]
+    pub tickets: TicketCount,
+}
+
+/// 제안자 입장에서 주는 것(`give`)과 받는 것(`take`)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TradeOffer {
+    #[serde(default)]
+    pub give: TradeSide,
+    #[serde(default)]
+    pub take: TradeSide,
+}
+
+/// 상대의 응답을 기다리는 거래
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Trade {
+    pub from: u32,
+    pub to: u32,
+    pub offer: TradeOffer,
+    /// 거래가 끝나면 돌아갈 상황
+    pub resume: GameSituation,
+}
+
+impl TicketCount {
+    fn covers(&self, other: &Self) -> bool {
+        self.free_hospital >= other.free_hospital
+            && self.free_property >= other.free_property
+            && self.double_lotto >= other.double_lotto
+            && self.no_tax >= other.no_tax
+            && self.release_from_jail >= other.release_from_jail
+            && self.bonus >= other.bonus
+    }
+}
+
+impl GameEngine {
+    /// `player_id`가 `side`를 내놓을 수 있는지 확인
+    fn validate_trade_side(&self, player_id: u32, side: &TradeSide) -> Result<(), GameError> {
+        let player = self.state.players.iter().find(|player| player.id == player_id).ok_or(GameError::UnknownPlayer(player_id))?;
+        if player.bankrupt {
+            return Err(GameError::InvalidTrade(format!("Player {} is bankrupt.", player_id)));
+        }
+        if side.money < 0 {
+            return Err(GameError::InvalidAmount(side.money));
+        }
+        if player.money < side.money {
+            return Err(GameError::InsufficientFunds { required: side.money, available: player.money });
+        }
+        let mut seen = HashSet::new();
+        for name in side.properties.iter() {
+            if !seen.insert(name) {
+                return Err(GameError::InvalidTrade(format!("'{}' is offered more than once.", name)));
+            }
+            match self.state.properties.get(name) {
+                Some(ownership) if ownership.owner_id == player_id => {},
+                Some(_) => return Err(GameError::InvalidTrade(format!("'{}' is not owned by Player {}.", name, player_id))),
+                None => return Err(GameError::UnownedProperty(name.clone())),
+            }
+        }
+        if !player.tickets_count.covers(&side.tickets) {
+            return Err(GameError::InvalidTrade(format!("Player {} does not have the offered tickets.", player_id)));
+        }
+        Ok(())
+    }
+
+    fn validate_trade(&self, from: u32, to: u32, offer: &TradeOffer) -> Result<(), GameError> {
+        if from == to {
+            return Err(GameError::InvalidTrade("A player cannot trade with themselves.".into()));
+        }
+        self.validate_trade_side(from, &offer.give)?;
+        self.validate_trade_side(to, &offer.take)
+    }
+
+    fn transfer_trade_side(&mut self, from: u32, to: u32, side: &TradeSide) {
+        for player_mut in self.state.players.iter_mut() {
+            if player_mut.id == from {
+                player_mut.money -= side.money;
+                player_mut.tickets_count -= side.tickets;
+            } else if player_mut.id == to {
+                player_mut.money += side.money;
+                player_mut.tickets_count += side.tickets;
+            }
+        }
+        for name in side.properties.iter() {
+            if let Some(ownership) = self.state.properties.get_mut(name) {
+                ownership.owner_id = to;
+            }
+        }
+    }
+
+    /// 현재 차례인 플레이어 `from`이 `to`에게 거래를 제안
+    pub fn propose_trade(&mut self, from: u32, to: u32, offer: TradeOffer) -> Result<(), GameError> {
+        self.expect(Command::ProposeTrade)?;
+        self.record(ReplayStep::ProposeTrade { from, to, offer: offer.clone() }, |game| {
+            let current_id = game.state.players[game.state.current_turn_idx].id;
+            if from != current_id {
+                return Err(GameError::InvalidTrade(format!("It is not Player {}'s turn.", from)));
+            }
+            game.validate_trade(from, to, &offer)?;
+
+            game.emit(GameEvent::TradeProposed { from, to });
+            game.state.pending_trade = Some(Trade { from, to, offer, resume: game.now });
+            game.now = GameSituation::PendingTradeResponse;
+            Ok(())
+        })
+    }
+
+    /// 응답하는 플레이어가 조건을 바꿔 되제안 (`offer`는 응답자 입장)
+    pub fn counter_trade(&mut self, offer: TradeOffer) -> Result<(), GameError> {
+        self.expect(Command::CounterTrade)?;
+        self.record(ReplayStep::CounterTrade { offer: offer.clone() }, |game| {
+            let Some(trade) = game.state.pending_trade.clone() else {
+                return Err(GameError::InvalidTrade("There is no pending trade.".into()));
+            };
+            game.validate_trade(trade.to, trade.from, &offer)?;
+
+            game.emit(GameEvent::TradeCountered { from: trade.to, to: trade.from });
+            game.state.pending_trade = Some(Trade { from: trade.to, to: trade.from, offer, resume: trade.resume });
+            Ok(())
+        })
+    }
+
+    /// 응답을 기다리는 거래
+    pub fn pending_trade(&self) -> Option<&Trade> {
+        self.state.pending_trade.as_ref()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    /// 거래를 받아들이고 양쪽의 자산을 맞바꿈
+    pub fn accept_trade(&mut self) -> Result<(), GameError> {
+        self.expect(Command::AcceptTrade)?;
+        self.record(ReplayStep::AcceptTrade, |game| {
+            let Some(trade) = game.state.pending_trade.clone() else {
+                return Err(GameError::InvalidTrade("There is no pending trade.".into()));
+            };
+            // 제안 이후 대출 상환 등으로 자산이 바뀌었을 수 있음
+            game.validate_trade(trade.from, trade.to, &trade.offer)?;
+
+            game.transfer_trade_side(trade.from, trade.to, &trade.offer.give);
+            game.transfer_trade_side(trade.to, trade.from, &trade.offer.take);
+            game.emit(GameEvent::TradeAccepted { from: trade.from, to: trade.to });
+            game.state.pending_trade = None;
+            game.now = trade.resume;
+            Ok(())
+        })
+    }
+
+    pub fn reject_trade(&mut self) -> Result<(), GameError> {
+        self.expect(Command::RejectTrade)?;
+        self.record(ReplayStep::RejectTrade, |game| {
+            let Some(trade) = game.state.pending_trade.take() else {
+                return Err(GameError::InvalidTrade("There is no pending trade.".into()));
+            };
+            game.emit(GameEvent::TradeRejected { from: trade.from, to: trade.to });
+            game.now = trade.resume;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GameEngine {
+    fn parse_trade_offer(offer_json: &str) -> Result<TradeOffer, GameError> {
+        serde_json::from_str(offer_json).map_err(|e| GameError::InvalidJson(e.to_string()))
+    }
+
+    /// `propose_trade` (`offer_json`은 `TradeOffer`의 JSON)
+    #[wasm_bindgen(js_name = propose_trade)]
+    pub fn propose_trade_json(&mut self, from: u32, to: u32, offer_json: &str) -> Result<(), GameError> {
+        self.propose_trade(from, to, Self::parse_trade_offer(offer_json)?)
+    }
+
+    /// `counter_trade` (`offer_json`은 응답자 입장의 `TradeOffer`의 JSON)
+    #[wasm_bindgen(js_name = counter_trade)]
+    pub fn counter_trade_json(&mut self, offer_json: &str) -> Result<(), GameError> {
+        self.counter_trade(Self::parse_trade_offer(offer_json)?)
+    }
+
+    /// 응답을 기다리는 거래 (`Trade`의 JSON, 없으면 `null`)
+    pub fn get_pending_trade(&self) -> String {
+        serde_json::to_string(&self.pending_trade()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, GameEngine, GameError, GameSituation, Ownership, TradeOffer, TradeSide};
+
+    /// 플레이어 1이 1100000을 주고 플레이어 2의 부산을 받겠다고 제안한 게임 (플레이어 1은 200000을 빌린 상태)
+    fn proposed() -> GameEngine {
+        let mut game = testing::game(&[]);
+        game.state.properties.insert("Busan".into(), Ownership { owner_id: 2, buildings: 0, mortgaged: false });
+        game.borrow_money(1, 200_000).unwrap();
+        let offer = TradeOffer {
+            give: TradeSide { money: 1_100_000, ..TradeSide::default() },
+            take: TradeSide { properties: vec!["Busan".into()], ..TradeSide::default() },
+        };
+        game.propose_trade(1, 2, offer).unwrap();
+        game
+    }
+
+    #[test]
+    fn accepted_trade_swaps_the_assets() {
+        let mut game = proposed();
+        game.accept_trade().unwrap();
+        assert_eq!(game.state.properties["Busan"].owner_id, 1);
+        assert_eq!((game.state.players[0].money, game.state.players[1].money), (100_000, 2_100_000));
+        assert_eq!(game.now, GameSituation::PendingRollResponse);
+    }
+
+    #[test]
+    fn trade_is_revalidated_when_accepted() {
+        let mut game = proposed();
+        let loan_id = game.state.players[0].remaining_loans[0].id;
+        game.repay_loan(1, loan_id, 200_000).unwrap();
+
+        assert!(matches!(game.accept_trade(), Err(GameError::InsufficientFunds { required: 1_100_000, available: 1_000_000 })));
+        assert_eq!(game.state.properties["Busan"].owner_id, 2);
+        assert_eq!((game.state.players[0].money, game.state.players[1].money), (1_000_000, 1_000_000));
+        assert!(game.pending_trade().is_some());
+        assert_eq!(game.now, GameSituation::PendingTradeResponse);
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameEngine, GameError, GameSnapshot, ReplayStep};
+use crate::save::state_hash;
+
+/// 명령을 되돌릴 수 있는 범위
+///
+/// 어느 경우에도 주사위를 굴리거나 난수를 쓴 명령은 되돌릴 수 없으므로, 되돌리기로 주사위를 다시 굴릴 수는 없다.
+/// 로케일이나 메시지 카탈로그를 바꾼 뒤에도 그 전의 명령은 되돌릴 수 없다.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum UndoPolicy {
+    /// 되돌릴 수 없음 (온라인 대전 등, 기본값이며 호스트가 `set_undo_policy`로 켬)
+    #[default]
+    Disabled,
+    /// 마지막으로 주사위를 굴리거나 난수를 쓴 뒤의 명령만 되돌림 (한 기기에서 번갈아 하는 핫시트)
+    UntilRandomness,
+    /// 위와 같되 턴을 넘긴 뒤에는 되돌릴 수 없음
+    CurrentTurn,
+}
+
+/// 되돌리기 항목: 명령들을 적용하기 전의 상태
+struct UndoEntry {
+    snapshot: GameSnapshot,
+    /// 이 항목으로 되돌리면 기록에서 지워지는 명령의 수
+    steps: usize,
+}
+
+/// 다시 실행 항목: 되돌리기 직전의 상태와 그때 지운 명령들
+struct RedoEntry {
+    snapshot: GameSnapshot,
+    steps: Vec<ReplayStep>,
+}
+
+#[derive(Default)]
+pub(crate) struct UndoHistory {
+    policy: UndoPolicy,
+    undo: Vec<UndoEntry>,
+    redo: Vec<RedoEntry>,
+}
+
+impl UndoHistory {
+    pub(crate) fn new(policy: UndoPolicy) -> Self {
+        Self { policy, ..Self::default() }
+    }
+}
+
+impl ReplayStep {
+    /// 주사위를 굴리거나, 주사위 눈으로 움직이거나, 난수를 쓰는 명령
+    pub fn uses_randomness(&self) -> bool {
+        matches!(self,
+            Self::RollDice { .. } | Self::RunTurnScript { .. } | Self::TryToJailbreakByDices { .. }
+            | Self::UseTicket { .. } | Self::LuckTest { .. } | Self::GetRandomChanceCard)
+    }
+}
+
+impl GameEngine {
+    /// 되돌리기 정책상 `step`을 되돌릴 수 있는지
+    pub(crate) fn is_undoable(&self, step: &ReplayStep) -> bool {
+        // 메시지 카탈로그는 스냅샷에 들어가지 않으므로 로케일을 바꾸면 그 전으로 되돌릴 수 없음
+        if matches!(step, ReplayStep::SetLocale { .. } | ReplayStep::LoadMessages { .. }) {
+            return false;
+        }
+        match self.undo.policy {
+            UndoPolicy::Disabled => false,
+            UndoPolicy::UntilRandomness => !step.uses_randomness(),
+            UndoPolicy::CurrentTurn => !step.uses_randomness() && !matches!(step, ReplayStep::EndTurn),
+        }
+    }
+
+    /// 성공한 명령의 직전 상태를 쌓음 (되돌릴 수 없는 명령이었다면 지금까지 쌓인 상태를 버림)
+    pub(crate) fn push_undo(&mut self, before: Option<GameSnapshot>) {
+        self.undo.redo.clear();
+        match before {
+            Some(snapshot) => self.undo.undo.push(UndoEntry { snapshot, steps: 1 }),
+            None => self.undo.undo.clear(),
+        }
+    }
+
+    /// 스냅샷의 상태로 돌아감 (이미 쓴 난수는 되돌리지 않음)
+    fn restore(&mut self, mut snapshot: GameSnapshot) {
+        snapshot.state.rng = self.state.rng.clone();
+        self.rollback(snapshot);
+    }
+
+    /// 실패한 명령을 적용하기 직전의 상태로 돌아감 (난수 생성기까지 되돌림)
+    pub(crate) fn rollback(&mut self, snapshot: GameSnapshot) {
+        self.state = snapshot.state;
+        self.now = snapshot.now;
+        self.pending_chance_card_id = snapshot.pending_chance_card_id;
+    }
+
+    /// 상태를 바꾼 마지막 명령을 되돌릴 수 있는 항목의 위치 (성공했지만 상태를 바꾸지 않은 명령은 건너뜀)
+    fn undo_target(&self) -> Option<usize> {
+        let current = state_hash(&self.snapshot());
+        self.undo.undo.iter().rposition(|entry| state_hash(&entry.snapshot) != current)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GameEngine {
+    pub fn set_undo_policy(&mut self, policy: UndoPolicy) {
+        self.undo = UndoHistory::new(policy);
+    }
+
+    pub fn get_undo_policy(&self) -> UndoPolicy {
+        self.undo.policy
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_target().is_some()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undo.redo.is_empty()
+    }
+
+    /// 상태를 바꾼 마지막 명령을 되돌리고, 리플레이 기록에서도 지움
+    pub fn undo(&mut self) -> Result<(), GameError> {
+        let index = self.undo_target().ok_or_else(|| GameError::UndoUnavailable("There is nothing to undo.".into()))?;
+        if self.undo.undo[index].snapshot.state.rng != self.state.rng {
+            return Err(GameError::UndoUnavailable("Randomness has been used since then.".into()));
+        }
+        let mut entries = self.undo.undo.split_off(index);
+        let steps = self.unrecord(entries.iter().map(|entry| entry.steps).sum());
+        let current = self.snapshot();
+        self.undo.redo.push(RedoEntry { snapshot: current, steps });
+        self.restore(entries.swap_remove(0).snapshot);
+        Ok(())
+    }
+
+    /// 마지막으로 되돌린 명령들을 다시 적용
+    pub fn redo(&mut self) -> Result<(), GameError> {
+        let RedoEntry { snapshot, steps } = self.undo.redo.pop().ok_or_else(|| GameError::UndoUnavailable("There is nothing to redo.".into()))?;
+        let current = self.snapshot();
+        self.undo.undo.push(UndoEntry { snapshot: current, steps: steps.len() });
+        self.rerecord(steps);
+        self.restore(snapshot);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing, GameError, UndoPolicy};
+
+    #[test]
+    fn undo_is_opt_in_and_skips_failed_commands() {
+        let mut game = testing::game(&[]);
+        assert_eq!(game.get_undo_policy(), UndoPolicy::Disabled);
+        game.borrow_money(1, 100_000).unwrap();
+        assert!(!game.can_undo());
+
+        game.set_undo_policy(UndoPolicy::UntilRandomness);
+        let steps = game.replay_document().steps.len();
+        assert!(matches!(game.borrow_money(1, -1), Err(GameError::InvalidAmount(-1))));
+        assert!(!game.can_undo());
+        assert_eq!(game.replay_document().steps.len(), steps);
+
+        game.borrow_money(1, 50_000).unwrap();
+        assert_eq!(game.replay_document().steps.len(), steps + 1);
+        game.undo().unwrap();
+        assert_eq!(game.state.players[0].money, 1_100_000);
+        assert_eq!(game.replay_document().steps.len(), steps);
+    }
+
+    #[test]
+    fn changing_the_locale_cannot_be_undone() {
+        let mut game = testing::game(&[]);
+        game.set_undo_policy(UndoPolicy::UntilRandomness);
+        game.borrow_money(1, 100_000).unwrap();
+        assert!(game.can_undo());
+
+        game.set_locale("ko").unwrap();
+        assert!(!game.can_undo());
+        assert!(matches!(game.undo(), Err(GameError::UndoUnavailable(_))));
+        assert_eq!(game.get_locale(), "ko");
+        assert_eq!(game.state.players[0].money, 1_100_000);
+    }
+}